@@ -3,22 +3,11 @@
 // file 'LICENSE', which is part of this source code package.
 
 use anyhow::Result;
-use std::io::{stdin, BufRead};
+use std::io::stdin;
 
 use day01::*;
 
-fn process(bufin: impl BufRead) -> Result<i32> {
-    let numbers = parse(bufin)?;
-    Ok(numbers.into_iter().sum())
-}
-
-#[test]
-fn test() -> Result<()> {
-    assert_eq!(process(EXAMPLE.as_bytes())?, 3);
-    Ok(())
-}
-
 fn main() -> Result<()> {
-    println!("{}", process(stdin().lock())?);
+    println!("{}", process_a(stdin().lock())?);
     Ok(())
 }
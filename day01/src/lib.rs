@@ -2,7 +2,11 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
+use anyhow::anyhow;
 use anyhow::Result;
+use std::collections;
+use std::fmt;
+use std::io;
 use std::io::BufRead;
 
 pub const EXAMPLE: &str = "+1
@@ -11,19 +15,368 @@ pub const EXAMPLE: &str = "+1
 +1
 ";
 
+/// Lazy iterator over the deltas of a `BufRead`, one or more per line
+/// separated by commas and/or whitespace (`+1, -2, +3`)
+pub struct Deltas<R> {
+    lines: io::Lines<R>,
+    lineno: usize,
+    pending: collections::VecDeque<Result<i32>>,
+}
+
+impl<R: BufRead> Iterator for Deltas<R> {
+    type Item = Result<i32>;
+    fn next(&mut self) -> Option<Result<i32>> {
+        while self.pending.is_empty() {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e.into())),
+            };
+            self.lineno += 1;
+            let lineno = self.lineno;
+            self.pending.extend(
+                line.split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|token| !token.is_empty())
+                    .map(|token| {
+                        token.parse::<i32>().map_err(|e| {
+                            anyhow!("line {}: invalid delta {:?}: {}", lineno, token, e)
+                        })
+                    }),
+            );
+        }
+        self.pending.pop_front()
+    }
+}
+
+pub fn deltas<R: BufRead>(bufin: R) -> Deltas<R> {
+    Deltas {
+        lines: bufin.lines(),
+        lineno: 0,
+        pending: Default::default(),
+    }
+}
+
+/// Lazy iterator over the frequencies reached applying each delta
+pub fn frequencies(bufin: impl BufRead) -> impl Iterator<Item = Result<i64>> {
+    deltas(bufin).scan(0_i64, |frequency, delta| {
+        Some(delta.map(|d| {
+            *frequency += i64::from(d);
+            *frequency
+        }))
+    })
+}
+
 pub fn parse(bufin: impl BufRead) -> Result<Vec<i32>> {
-    bufin
-        .lines()
-        .map(|line_opt| {
-            let line = line_opt?;
-            let i: i32 = line.parse()?;
-            Ok(i)
-        })
-        .collect()
+    deltas(bufin).collect()
 }
 
 #[test]
 fn test() -> Result<()> {
     assert_eq!(parse(EXAMPLE.as_bytes())?, &[1, -2, 3, 1]);
+    assert_eq!(
+        parse("+1, -2, +3\n+1 +1\n\n".as_bytes())?,
+        &[1, -2, 3, 1, 1]
+    );
+    let e = parse("+1\n+1, x\n".as_bytes()).unwrap_err();
+    assert!(
+        e.to_string().starts_with("line 2: invalid delta \"x\""),
+        "{}",
+        e
+    );
+    let freqs = frequencies(EXAMPLE.as_bytes()).collect::<Result<Vec<_>>>()?;
+    assert_eq!(freqs, &[1, -1, 2, 3]);
+    Ok(())
+}
+
+pub fn process_a(bufin: impl BufRead) -> Result<i32> {
+    deltas(bufin).try_fold(0_i32, |sum, delta| {
+        sum.checked_add(delta?)
+            .ok_or_else(|| anyhow!("frequency overflow"))
+    })
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    assert_eq!(process_a(EXAMPLE.as_bytes())?, 3);
+    Ok(())
+}
+
+/// The first frequency reached twice when applying the deltas over and
+/// over, with the pass and the index of the delta that reaches it again
+/// (both starting at 0)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FirstRepeat {
+    Found {
+        frequency: i64,
+        pass: usize,
+        index: usize,
+    },
+    Never,
+}
+
+/// Finds the first repeated frequency without iterating the passes.
+///
+/// Every frequency is a partial sum of the first pass plus a multiple
+/// of the drift (the sum of all deltas), so a frequency can only repeat
+/// one with the same residue modulo the drift. Grouping the partial
+/// sums by residue and sorting them, the first repeat of each sum is
+/// its neighbor in the drift direction, `(neighbor - sum) / drift`
+/// passes later.
+pub fn first_repeat(deltas: &[i32]) -> FirstRepeat {
+    let sums = deltas
+        .iter()
+        .scan(0_i64, |sum, &d| {
+            *sum += i64::from(d);
+            Some(*sum)
+        })
+        .collect::<Vec<_>>();
+    // Repeats inside the first pass come before any other
+    let mut visited = collections::HashSet::new();
+    for (index, &sum) in sums.iter().enumerate() {
+        if !visited.insert(sum) {
+            return FirstRepeat::Found {
+                frequency: sum,
+                pass: 0,
+                index,
+            };
+        }
+    }
+    let Some(&drift) = sums.last() else {
+        return FirstRepeat::Never;
+    };
+    if drift == 0 {
+        return FirstRepeat::Found {
+            frequency: sums[0],
+            pass: 1,
+            index: 0,
+        };
+    }
+    let mut residues = collections::HashMap::<i64, Vec<(i64, usize)>>::new();
+    for (index, &sum) in sums.iter().enumerate() {
+        residues
+            .entry(sum.rem_euclid(drift.abs()))
+            .or_default()
+            .push((sum, index));
+    }
+    let mut best: Option<(u64, usize, i64)> = None;
+    for group in residues.values_mut() {
+        // Sorted in the drift direction
+        group.sort_by_key(|&(sum, _)| sum * drift.signum());
+        for pair in group.windows(2) {
+            let ((sum, index), (frequency, _)) = (pair[0], pair[1]);
+            let passes = (frequency - sum).unsigned_abs() / drift.unsigned_abs();
+            if best.is_none_or(|b| (passes, index) < (b.0, b.1)) {
+                best = Some((passes, index, frequency));
+            }
+        }
+    }
+    match best {
+        Some((passes, index, frequency)) => FirstRepeat::Found {
+            frequency,
+            pass: passes as usize,
+            index,
+        },
+        None => FirstRepeat::Never,
+    }
+}
+
+/// The original solution: apply the deltas until a frequency repeats,
+/// here bounded to a number of passes
+#[cfg(test)]
+fn first_repeat_loop(numbers: &[i32], passes: usize) -> FirstRepeat {
+    let mut sum = 0_i64;
+    let mut visited = collections::HashSet::new();
+    for pass in 0..passes {
+        for (index, &number) in numbers.iter().enumerate() {
+            sum += i64::from(number);
+            if visited.contains(&sum) {
+                return FirstRepeat::Found {
+                    frequency: sum,
+                    pass,
+                    index,
+                };
+            }
+            visited.insert(sum);
+        }
+    }
+    FirstRepeat::Never
+}
+
+#[test]
+fn test_first_repeat() {
+    let found = |frequency, pass, index| FirstRepeat::Found {
+        frequency,
+        pass,
+        index,
+    };
+    assert_eq!(first_repeat(&[1, -2, 3, 1]), found(2, 1, 1));
+    assert_eq!(first_repeat(&[1, -1]), found(1, 1, 0));
+    assert_eq!(first_repeat(&[3, 3, 4, -2, -4]), found(10, 1, 1));
+    assert_eq!(first_repeat(&[-6, 3, 8, 5, -6]), found(5, 2, 1));
+    assert_eq!(first_repeat(&[7, 7, -2, -7, -4]), found(14, 2, 2));
+    assert_eq!(first_repeat(&[1, 1, -1]), found(1, 0, 2));
+    assert_eq!(first_repeat(&[1, 1]), FirstRepeat::Never);
+    assert_eq!(first_repeat(&[]), FirstRepeat::Never);
+    // Differential test against the loop, with pseudo-random deltas
+    testrand::differential(
+        2000,
+        |rng| {
+            let len = 1 + rng.below(12) as usize;
+            let range = 1 + rng.below(30) as i32;
+            let deltas = (0..len)
+                .map(|_| rng.below(2 * range as u64 + 1) as i32 - range)
+                .collect::<Vec<_>>();
+            (deltas, 2 * range as usize * len + 2)
+        },
+        // The loop gives up after the passes
+        |(deltas, passes)| match first_repeat(deltas) {
+            FirstRepeat::Found { pass, .. } if pass >= *passes => FirstRepeat::Never,
+            got => got,
+        },
+        |(deltas, passes)| first_repeat_loop(deltas, *passes),
+    );
+}
+
+/// A frequency reached again, at the pass and index of the delta
+/// (both starting at 0)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Repeat {
+    pub frequency: i64,
+    pub pass: usize,
+    pub index: usize,
+}
+
+/// How the frequency evolves over the passes through the deltas
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DriftReport {
+    pub deltas: usize,
+    /// Lowest and highest frequencies reached in the first pass
+    pub range: Option<(i64, i64)>,
+    /// Frequency change of each full pass
+    pub drift: i64,
+    pub first_repeat: FirstRepeat,
+    /// Number of passes considered for `repeats`
+    pub passes: usize,
+    /// Every frequency that repeats in the first `passes` passes, at
+    /// its first repetition
+    pub repeats: Vec<Repeat>,
+}
+
+impl DriftReport {
+    pub fn new(deltas: &[i32], passes: usize) -> DriftReport {
+        let mut range: Option<(i64, i64)> = None;
+        let mut frequency = 0_i64;
+        let mut visited = collections::HashSet::new();
+        let mut repeated = collections::HashSet::new();
+        let mut repeats = vec![];
+        for pass in 0..passes {
+            for (index, &delta) in deltas.iter().enumerate() {
+                frequency += i64::from(delta);
+                if pass == 0 {
+                    range = Some(range.map_or((frequency, frequency), |(min, max)| {
+                        (min.min(frequency), max.max(frequency))
+                    }));
+                }
+                if !visited.insert(frequency) && repeated.insert(frequency) {
+                    repeats.push(Repeat {
+                        frequency,
+                        pass,
+                        index,
+                    });
+                }
+            }
+        }
+        DriftReport {
+            deltas: deltas.len(),
+            range,
+            drift: deltas.iter().map(|&d| i64::from(d)).sum(),
+            first_repeat: first_repeat(deltas),
+            passes,
+            repeats,
+        }
+    }
+
+    /// Number of passes needed to reach the first repeat
+    pub fn passes_to_repeat(&self) -> Option<usize> {
+        match self.first_repeat {
+            FirstRepeat::Found { pass, .. } => Some(pass + 1),
+            FirstRepeat::Never => None,
+        }
+    }
+}
+
+impl fmt::Display for DriftReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "deltas: {}", self.deltas)?;
+        if let Some((min, max)) = self.range {
+            writeln!(f, "first pass: min {}, max {}", min, max)?;
+        }
+        writeln!(f, "drift per pass: {:+}", self.drift)?;
+        match self.first_repeat {
+            FirstRepeat::Found {
+                frequency,
+                pass,
+                index,
+            } => writeln!(
+                f,
+                "first repeat: {} at pass {}, delta {} ({} passes)",
+                frequency,
+                pass,
+                index,
+                pass + 1
+            )?,
+            FirstRepeat::Never => writeln!(f, "first repeat: never")?,
+        }
+        writeln!(
+            f,
+            "repeats in {} passes: {}",
+            self.passes,
+            self.repeats.len()
+        )?;
+        for r in &self.repeats {
+            writeln!(f, "  {} at pass {}, delta {}", r.frequency, r.pass, r.index)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_report() -> Result<()> {
+    let report = DriftReport::new(&parse(EXAMPLE.as_bytes())?, 3);
+    assert_eq!(report.range, Some((-1, 3)));
+    assert_eq!(report.drift, 3);
+    assert_eq!(report.passes_to_repeat(), Some(2));
+    assert_eq!(
+        report.repeats,
+        vec![
+            Repeat {
+                frequency: 2,
+                pass: 1,
+                index: 1
+            },
+            Repeat {
+                frequency: 5,
+                pass: 2,
+                index: 1
+            },
+        ]
+    );
+    let report = DriftReport::new(&[1, 1], 5);
+    assert_eq!(report.passes_to_repeat(), None);
+    assert!(report.repeats.is_empty());
+    assert!(report.to_string().contains("first repeat: never"));
+    Ok(())
+}
+
+pub fn process_b(bufin: impl BufRead) -> Result<i64> {
+    let numbers = parse(bufin)?;
+    match first_repeat(&numbers) {
+        FirstRepeat::Found { frequency, .. } => Ok(frequency),
+        FirstRepeat::Never => Err(anyhow!("no sum visited twice")),
+    }
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    assert_eq!(process_b(EXAMPLE.as_bytes())?, 2);
     Ok(())
 }
@@ -2,7 +2,9 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
+use anyhow::anyhow;
 use anyhow::Result;
+use std::collections;
 use std::io::BufRead;
 
 pub const EXAMPLE1: &str = "abcdef
@@ -32,3 +34,215 @@ fn test() -> Result<()> {
     );
     Ok(())
 }
+
+/// Number of occurrences of each letter of an ID
+pub type Histogram = collections::HashMap<char, usize>;
+
+pub fn histogram(id: &str) -> Histogram {
+    id.chars().fold(Histogram::new(), |mut freqs, c| {
+        *freqs.entry(c).or_insert(0) += 1;
+        freqs
+    })
+}
+
+/// For each multiplicity, counts the IDs that have a letter appearing
+/// exactly that many times, and multiplies the counts
+pub fn checksum<S: AsRef<str>>(ids: &[S], multiplicities: &[usize]) -> usize {
+    let histograms = ids
+        .iter()
+        .map(|id| histogram(id.as_ref()))
+        .collect::<Vec<_>>();
+    multiplicities
+        .iter()
+        .map(|m| {
+            histograms
+                .iter()
+                .filter(|h| h.values().any(|n| n == m))
+                .count()
+        })
+        .product()
+}
+
+/// Checks that all IDs have the same number of letters
+pub fn validate_lengths<S: AsRef<str>>(ids: &[S]) -> Result<()> {
+    let mut lengths = ids.iter().map(|id| id.as_ref().chars().count());
+    let Some(expected) = lengths.next() else {
+        return Ok(());
+    };
+    for (i, len) in lengths.enumerate() {
+        if len != expected {
+            return Err(anyhow!(
+                "line {}: ID {:?} has {} letters, expected {} as in line 1",
+                i + 2,
+                ids[i + 1].as_ref(),
+                len,
+                expected
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_checksum() {
+    let ids = parse(EXAMPLE1.as_bytes()).unwrap();
+    assert_eq!(histogram("bababc")[&'b'], 3);
+    assert_eq!(checksum(&ids, &[2, 3]), 12);
+    assert_eq!(checksum(&ids, &[1]), 6);
+    assert_eq!(checksum(&["ééa", "aéb", "ééé"], &[2, 3]), 1);
+    assert!(validate_lengths(&ids).is_ok());
+    assert!(validate_lengths(&["aé", "éa"]).is_ok());
+    let e = validate_lengths(&["abc", "abd", "ab"]).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "line 3: ID \"ab\" has 2 letters, expected 3 as in line 1"
+    );
+}
+
+pub fn process_a(bufin: impl BufRead) -> Result<usize> {
+    let codes = parse(bufin)?;
+    Ok(checksum(&codes, &[2, 3]))
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    assert_eq!(process_a(EXAMPLE1.as_bytes())?, 12);
+    Ok(())
+}
+
+/// Two IDs that differ in at most k positions, by their indexes in the
+/// input, with the letters they have in common and the positions where
+/// they differ
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NearDuplicate {
+    pub first: usize,
+    pub second: usize,
+    pub common: String,
+    pub differing: Vec<usize>,
+}
+
+/// Finds all pairs of IDs of the same length that differ in at most `k`
+/// positions.
+///
+/// Splitting the IDs in k + 1 blocks, two IDs that differ in at most k
+/// positions must be equal in at least one of the blocks, so we only
+/// compare the IDs that share a block in the same position.
+pub fn near_duplicates<S: AsRef<str>>(ids: &[S], k: usize) -> Vec<NearDuplicate> {
+    let ids = ids
+        .iter()
+        .map(|id| id.as_ref().chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let mut index = collections::HashMap::<_, Vec<usize>>::new();
+    for (i, id) in ids.iter().enumerate() {
+        let len = id.len();
+        for block in 0..=k {
+            let range = block * len / (k + 1)..(block + 1) * len / (k + 1);
+            index.entry((len, block, &id[range])).or_default().push(i);
+        }
+    }
+    let mut candidates = collections::BTreeSet::new();
+    for bucket in index.values() {
+        for (n, &first) in bucket.iter().enumerate() {
+            for &second in &bucket[n + 1..] {
+                candidates.insert((first, second));
+            }
+        }
+    }
+    candidates
+        .into_iter()
+        .filter_map(|(first, second)| {
+            let (id1, id2) = (&ids[first], &ids[second]);
+            let differing = (0..id1.len())
+                .filter(|&i| id1[i] != id2[i])
+                .collect::<Vec<_>>();
+            (differing.len() <= k).then(|| NearDuplicate {
+                first,
+                second,
+                common: (0..id1.len())
+                    .filter(|&i| id1[i] == id2[i])
+                    .map(|i| id1[i])
+                    .collect(),
+                differing,
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_near_duplicates() {
+    let ids = [
+        "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+    ];
+    assert_eq!(
+        near_duplicates(&ids, 1),
+        vec![NearDuplicate {
+            first: 1,
+            second: 4,
+            common: "fgij".to_string(),
+            differing: vec![2],
+        }]
+    );
+    let pairs = near_duplicates(&ids, 2)
+        .into_iter()
+        .map(|d| (d.first, d.second, d.differing))
+        .collect::<Vec<_>>();
+    assert_eq!(pairs, vec![(0, 5, vec![1, 3]), (1, 4, vec![2])]);
+    assert_eq!(near_duplicates(&["ab", "ab", "abc"], 0).len(), 1);
+    // Compare with all pairs, using pseudo-random IDs
+    testrand::differential(
+        20,
+        |rng| {
+            let ids = (0..1 + rng.below(100))
+                .map(|_| {
+                    (0..6)
+                        .map(|_| char::from(b'a' + rng.below(3) as u8))
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+            (ids, rng.below(4) as usize)
+        },
+        |(ids, k)| {
+            near_duplicates(ids, *k)
+                .into_iter()
+                .map(|d| (d.first, d.second))
+                .collect::<Vec<_>>()
+        },
+        |(ids, k)| {
+            (0..ids.len())
+                .flat_map(|i| (i + 1..ids.len()).map(move |j| (i, j)))
+                .filter(|&(i, j)| {
+                    ids[i]
+                        .chars()
+                        .zip(ids[j].chars())
+                        .filter(|(a, b)| a != b)
+                        .count()
+                        <= *k
+                })
+                .collect::<Vec<_>>()
+        },
+    );
+}
+
+pub fn process_b(bufin: impl BufRead) -> Result<String> {
+    let codes = parse(bufin)?;
+    validate_lengths(&codes)?;
+    near_duplicates(&codes, 1)
+        .into_iter()
+        .find(|d| d.differing.len() == 1)
+        .map(|d| d.common)
+        .ok_or_else(|| anyhow!("no correct code found"))
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    const EXAMPLE: &str = "abcde
+fghij
+klmno
+pqrst
+fguij
+axcye
+wvxyz
+";
+    assert_eq!(process_b(EXAMPLE.as_bytes())?, "fgij");
+    Ok(())
+}
@@ -2,16 +2,84 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-#[cfg(test)]
+use anyhow::anyhow;
 use anyhow::Result;
-
-type Claim = (usize, (i32, i32), (i32, i32));
+use std::fmt;
+use std::io::BufRead;
 
 pub const EXAMPLE: &str = "#1 @ 1,3: 4x4
 #2 @ 3,1: 4x4
 #3 @ 5,5: 2x2
 ";
 
+/// Rectangle of square inches, from (x0, y0) inclusive to (x1, y1)
+/// exclusive
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Rect {
+    pub x0: i64,
+    pub y0: i64,
+    pub x1: i64,
+    pub y1: i64,
+}
+
+impl Rect {
+    /// Rectangle with the top-left corner at `pos` and the given size
+    pub fn new(pos: (i64, i64), size: (i64, i64)) -> Rect {
+        Rect {
+            x0: pos.0,
+            y0: pos.1,
+            x1: pos.0 + size.0,
+            y1: pos.1 + size.1,
+        }
+    }
+
+    pub fn width(&self) -> i64 {
+        (self.x1 - self.x0).max(0)
+    }
+
+    pub fn height(&self) -> i64 {
+        (self.y1 - self.y0).max(0)
+    }
+
+    pub fn area(&self) -> u64 {
+        (self.width() * self.height()) as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.area() == 0
+    }
+
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let r = Rect {
+            x0: self.x0.max(other.x0),
+            y0: self.y0.max(other.y0),
+            x1: self.x1.min(other.x1),
+            y1: self.y1.min(other.y1),
+        };
+        (!r.is_empty()).then_some(r)
+    }
+
+    pub fn contains(&self, (x, y): (i64, i64)) -> bool {
+        self.x0 <= x && x < self.x1 && self.y0 <= y && y < self.y1
+    }
+
+    /// Smallest rectangle that contains both
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            x0: self.x0.min(other.x0),
+            y0: self.y0.min(other.y0),
+            x1: self.x1.max(other.x1),
+            y1: self.y1.max(other.y1),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Claim {
+    pub id: usize,
+    pub rect: Rect,
+}
+
 pub mod parser {
     use anyhow::anyhow;
     use anyhow::Result;
@@ -22,21 +90,22 @@ pub mod parser {
     use nom::IResult;
     use std::io::BufRead;
 
-    use super::Claim;
+    use super::{Claim, Rect};
 
     pub fn line(input: &str) -> IResult<&str, Claim> {
         let (input, _) = bytes::tag("#")(input)?;
-        let (input, id) = combinator::map(character::i32, |i| i as usize)(input)?;
+        let (input, id) = combinator::map(character::u32, |i| i as usize)(input)?;
         let (input, _) = bytes::tag(" @ ")(input)?;
         let (input, x) = character::i32(input)?;
         let (input, _) = bytes::tag(",")(input)?;
         let (input, y) = character::i32(input)?;
         let (input, _) = bytes::tag(": ")(input)?;
-        let (input, w) = character::i32(input)?;
+        let (input, w) = character::u32(input)?;
         let (input, _) = bytes::tag("x")(input)?;
-        let (input, h) = character::i32(input)?;
+        let (input, h) = character::u32(input)?;
         let (input, _) = character::newline(input)?;
-        Ok((input, (id, (x, y), (w, h))))
+        let rect = Rect::new((x.into(), y.into()), (w.into(), h.into()));
+        Ok((input, Claim { id, rect }))
     }
 
     pub fn parse(mut bufin: impl BufRead) -> Result<Vec<Claim>> {
@@ -51,13 +120,638 @@ pub mod parser {
 
 #[test]
 fn test() -> Result<()> {
+    let claim = |id, pos, size| Claim {
+        id,
+        rect: Rect::new(pos, size),
+    };
     assert_eq!(
         parser::parse(EXAMPLE.as_bytes())?,
         &[
-            (1, (1, 3), (4, 4)),
-            (2, (3, 1), (4, 4)),
-            (3, (5, 5), (2, 2)),
+            claim(1, (1, 3), (4, 4)),
+            claim(2, (3, 1), (4, 4)),
+            claim(3, (5, 5), (2, 2)),
         ]
     );
+    assert!(parser::parse("#1 @ 1,3: -4x4\n".as_bytes()).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_rect() {
+    let r1 = Rect::new((1, 3), (4, 4));
+    let r2 = Rect::new((3, 1), (4, 4));
+    assert_eq!(r1.area(), 16);
+    assert_eq!(r1.intersection(&r2), Some(Rect::new((3, 3), (2, 2))));
+    assert_eq!(r1.intersection(&Rect::new((5, 5), (2, 2))), None);
+    assert!(r1.contains((4, 6)));
+    assert!(!r1.contains((5, 6)));
+    assert!(Rect::new((0, 0), (0, 3)).is_empty());
+}
+
+/// Sorted and deduplicated coordinates, mapping them to indexes
+fn compress(coords: impl Iterator<Item = i64>) -> Vec<i64> {
+    let mut coords = coords.collect::<Vec<_>>();
+    coords.sort_unstable();
+    coords.dedup();
+    coords
+}
+
+fn rank(coords: &[i64], c: i64) -> usize {
+    coords.binary_search(&c).unwrap()
+}
+
+/// Segment tree over the elementary intervals between the compressed
+/// y coordinates, with the length covered at least once and twice
+struct Coverage {
+    ys: Vec<i64>,
+    count: Vec<u32>,
+    once: Vec<i64>,
+    twice: Vec<i64>,
+}
+
+impl Coverage {
+    fn new(ys: Vec<i64>) -> Coverage {
+        let size = 4 * ys.len().max(1);
+        Coverage {
+            ys,
+            count: vec![0; size],
+            once: vec![0; size],
+            twice: vec![0; size],
+        }
+    }
+
+    /// Adds `delta` to the coverage of the intervals in [lo, hi)
+    fn update(
+        &mut self,
+        node: usize,
+        (l, r): (usize, usize),
+        (lo, hi): (usize, usize),
+        delta: i32,
+    ) {
+        if hi <= l || r <= lo {
+            return;
+        }
+        if lo <= l && r <= hi {
+            self.count[node] = self.count[node].wrapping_add_signed(delta);
+        } else {
+            let m = (l + r) / 2;
+            self.update(2 * node, (l, m), (lo, hi), delta);
+            self.update(2 * node + 1, (m, r), (lo, hi), delta);
+        }
+        let total = self.ys[r] - self.ys[l];
+        let leaf = r - l == 1;
+        let (once, twice) = if leaf {
+            (0, 0)
+        } else {
+            (
+                self.once[2 * node] + self.once[2 * node + 1],
+                self.twice[2 * node] + self.twice[2 * node + 1],
+            )
+        };
+        (self.once[node], self.twice[node]) = match self.count[node] {
+            0 => (once, twice),
+            1 => (total, once),
+            _ => (total, total),
+        };
+    }
+
+    fn add(&mut self, (y0, y1): (i64, i64), delta: i32) {
+        let range = (rank(&self.ys, y0), rank(&self.ys, y1));
+        let n = self.ys.len() - 1;
+        self.update(1, (0, n), range, delta);
+    }
+}
+
+/// Area claimed by two or more claims, sweeping a vertical line over
+/// the edges of the claims while a segment tree keeps the length of the
+/// line that is covered at least twice
+pub fn overlap_area(claims: &[Claim]) -> u64 {
+    let rects = claims
+        .iter()
+        .map(|c| c.rect)
+        .filter(|r| !r.is_empty())
+        .collect::<Vec<_>>();
+    if rects.is_empty() {
+        return 0;
+    }
+    let ys = compress(rects.iter().flat_map(|r| [r.y0, r.y1]));
+    let mut events = rects
+        .iter()
+        .flat_map(|r| [(r.x0, 1, (r.y0, r.y1)), (r.x1, -1, (r.y0, r.y1))])
+        .collect::<Vec<_>>();
+    events.sort_unstable();
+    let mut coverage = Coverage::new(ys);
+    let mut area = 0;
+    let mut x = events[0].0;
+    for (ex, delta, yy) in events {
+        area += (coverage.twice[1] * (ex - x)) as u64;
+        x = ex;
+        coverage.add(yy, delta);
+    }
+    area
+}
+
+/// Segment tree with the maximum y1 of the y-intervals indexed by the
+/// rank of their y0
+struct MaxEnd(Vec<i64>);
+
+impl MaxEnd {
+    fn new(size: usize) -> MaxEnd {
+        MaxEnd(vec![i64::MIN; 2 * size.next_power_of_two()])
+    }
+
+    fn set(&mut self, i: usize, value: i64) {
+        let mut i = i + self.0.len() / 2;
+        self.0[i] = value;
+        while i > 1 {
+            i /= 2;
+            self.0[i] = self.0[2 * i].max(self.0[2 * i + 1]);
+        }
+    }
+
+    /// Index and value of the maximum among the first `n` entries
+    fn max_prefix(&self, n: usize) -> Option<(usize, i64)> {
+        if n == 0 {
+            return None;
+        }
+        let (mut lo, mut hi) = (self.0.len() / 2, self.0.len() / 2 + n);
+        let mut best: Option<(usize, i64)> = None;
+        let mut consider = |node: usize| {
+            if best.is_none_or(|b| self.0[node] > b.1) {
+                best = Some((node, self.0[node]));
+            }
+        };
+        while lo < hi {
+            if lo % 2 == 1 {
+                consider(lo);
+                lo += 1;
+            }
+            if hi % 2 == 1 {
+                hi -= 1;
+                consider(hi);
+            }
+            lo /= 2;
+            hi /= 2;
+        }
+        let (mut node, value) = best?;
+        while node < self.0.len() / 2 {
+            node = if self.0[2 * node] == value {
+                2 * node
+            } else {
+                2 * node + 1
+            };
+        }
+        Some((node - self.0.len() / 2, value))
+    }
+}
+
+/// The claims that don't overlap any other, in input order.
+///
+/// Sweeps a vertical line over the claims keeping the y-intervals of
+/// the active ones in two trees indexed by y0 with the maximum y1: one
+/// with all of them, to check if a new claim overlaps any, and one with
+/// the ones not known to overlap yet, which are removed as they are
+/// found overlapping. Each claim is found at most once, and every
+/// operation is O(log n).
+pub fn non_overlapping(claims: &[Claim]) -> Vec<Claim> {
+    let mut order = (0..claims.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| (claims[i].rect.y0, i));
+    let mut ranks = vec![0; claims.len()];
+    for (r, &i) in order.iter().enumerate() {
+        ranks[i] = r;
+    }
+    let y0s = order.iter().map(|&i| claims[i].rect.y0).collect::<Vec<_>>();
+    let mut events = claims
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.rect.is_empty())
+        .flat_map(|(i, c)| [(c.rect.x0, 1, i), (c.rect.x1, 0, i)])
+        .collect::<Vec<_>>();
+    // Claims that end at x are removed before the ones that start at x
+    events.sort_unstable();
+    let mut all = MaxEnd::new(claims.len());
+    let mut unmarked = MaxEnd::new(claims.len());
+    let mut overlaps = vec![false; claims.len()];
+    for (_, start, i) in events {
+        let rect = claims[i].rect;
+        if start == 0 {
+            all.set(ranks[i], i64::MIN);
+            unmarked.set(ranks[i], i64::MIN);
+            continue;
+        }
+        // Active claims with y0 < rect.y1 that overlap have y1 > rect.y0
+        let n = y0s.partition_point(|&y0| y0 < rect.y1);
+        while let Some((r, y1)) = unmarked.max_prefix(n) {
+            if y1 <= rect.y0 {
+                break;
+            }
+            overlaps[order[r]] = true;
+            unmarked.set(r, i64::MIN);
+        }
+        if all.max_prefix(n).is_some_and(|(_, y1)| y1 > rect.y0) {
+            overlaps[i] = true;
+        } else {
+            unmarked.set(ranks[i], rect.y1);
+        }
+        all.set(ranks[i], rect.y1);
+    }
+    claims
+        .iter()
+        .zip(overlaps)
+        .filter(|(_, o)| !o)
+        .map(|(c, _)| *c)
+        .collect()
+}
+
+#[test]
+fn test_sweep() -> Result<()> {
+    let claims = parser::parse(EXAMPLE.as_bytes())?;
+    assert_eq!(overlap_area(&claims), 4);
+    assert_eq!(non_overlapping(&claims), vec![claims[2]]);
+    // Compare with the square inch map, using pseudo-random claims
+    testrand::differential(
+        200,
+        |rng| {
+            let mut random = |max: u64| rng.below(max) as i64;
+            (0..1 + random(12) as usize)
+                .map(|id| Claim {
+                    id,
+                    rect: Rect::new((random(20), random(20)), (random(8), random(8))),
+                })
+                .collect::<Vec<_>>()
+        },
+        |claims| (overlap_area(claims), non_overlapping(claims)),
+        |claims| {
+            let mut map = std::collections::HashMap::<_, Vec<usize>>::new();
+            for c in claims {
+                for x in c.rect.x0..c.rect.x1 {
+                    for y in c.rect.y0..c.rect.y1 {
+                        map.entry((x, y)).or_default().push(c.id);
+                    }
+                }
+            }
+            let area = map.values().filter(|ids| ids.len() > 1).count() as u64;
+            let single = claims
+                .iter()
+                .filter(|c| {
+                    map.values()
+                        .all(|ids| ids.len() == 1 || !ids.contains(&c.id))
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            (area, single)
+        },
+    );
+    Ok(())
+}
+
+/// Children of each R-tree node
+const FANOUT: usize = 8;
+
+#[derive(Debug, Clone)]
+struct Node {
+    bbox: Rect,
+    /// Indexes in `ClaimIndex::nodes`, or in `ClaimIndex::leaves` if
+    /// the node is a leaf
+    children: std::ops::Range<usize>,
+    leaf: bool,
+}
+
+/// Sorts the items by tiles of nearby centers: vertical slices ordered
+/// by x, each one ordered by y (sort-tile-recursive)
+fn tile<T>(items: &mut [T], bbox: impl Fn(&T) -> Rect) {
+    let center = |r: Rect| (r.x0 + r.x1, r.y0 + r.y1);
+    let groups = items.len().div_ceil(FANOUT);
+    let slice = FANOUT * (groups as f64).sqrt().ceil() as usize;
+    items.sort_by_key(|i| center(bbox(i)).0);
+    for chunk in items.chunks_mut(slice.max(1)) {
+        chunk.sort_by_key(|i| center(bbox(i)).1);
+    }
+}
+
+/// Static R-tree of claims for point, rectangle and overlap queries
+#[derive(Debug, Clone)]
+pub struct ClaimIndex {
+    claims: Vec<Claim>,
+    /// Claim indexes in the order of the leaves
+    leaves: Vec<usize>,
+    /// Nodes of all levels, with the root last
+    nodes: Vec<Node>,
+}
+
+impl ClaimIndex {
+    pub fn new(claims: Vec<Claim>) -> ClaimIndex {
+        let mut leaves = (0..claims.len()).collect::<Vec<_>>();
+        tile(&mut leaves, |&i| claims[i].rect);
+        let bbox = |range: std::ops::Range<usize>, rect: &dyn Fn(usize) -> Rect| {
+            range.clone().map(rect).reduce(|a, b| a.union(&b)).unwrap()
+        };
+        let mut level = (0..leaves.len())
+            .step_by(FANOUT)
+            .map(|start| {
+                let children = start..(start + FANOUT).min(leaves.len());
+                Node {
+                    bbox: bbox(children.clone(), &|i| claims[leaves[i]].rect),
+                    children,
+                    leaf: true,
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut nodes = vec![];
+        while level.len() > 1 {
+            tile(&mut level, |n| n.bbox);
+            let base = nodes.len();
+            nodes.append(&mut level);
+            level = (base..nodes.len())
+                .step_by(FANOUT)
+                .map(|start| {
+                    let children = start..(start + FANOUT).min(nodes.len());
+                    Node {
+                        bbox: bbox(children.clone(), &|i| nodes[i].bbox),
+                        children,
+                        leaf: false,
+                    }
+                })
+                .collect();
+        }
+        nodes.extend(level);
+        ClaimIndex {
+            claims,
+            leaves,
+            nodes,
+        }
+    }
+
+    pub fn claims(&self) -> &[Claim] {
+        &self.claims
+    }
+
+    /// Indexes of the claims whose rectangle satisfies `hit`, visiting
+    /// only the nodes that satisfy `visit`
+    fn search(&self, visit: impl Fn(&Rect) -> bool, hit: impl Fn(&Rect) -> bool) -> Vec<usize> {
+        let mut found = vec![];
+        let mut stack = self
+            .nodes
+            .len()
+            .checked_sub(1)
+            .into_iter()
+            .collect::<Vec<_>>();
+        while let Some(inode) = stack.pop() {
+            let node = &self.nodes[inode];
+            if !visit(&node.bbox) {
+                continue;
+            }
+            if node.leaf {
+                found.extend(
+                    self.leaves[node.children.clone()]
+                        .iter()
+                        .filter(|&&i| hit(&self.claims[i].rect)),
+                );
+            } else {
+                stack.extend(node.children.clone());
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+
+    /// Claims that cover the square inch at `pos`, in input order
+    pub fn at(&self, pos: (i64, i64)) -> Vec<&Claim> {
+        self.search(|r| r.contains(pos), |r| r.contains(pos))
+            .into_iter()
+            .map(|i| &self.claims[i])
+            .collect()
+    }
+
+    /// Claims that share some area with `rect`, in input order
+    pub fn intersecting(&self, rect: &Rect) -> Vec<&Claim> {
+        let hit = |r: &Rect| r.intersection(rect).is_some();
+        self.search(hit, hit)
+            .into_iter()
+            .map(|i| &self.claims[i])
+            .collect()
+    }
+
+    /// Claims that overlap at least `k` other claims, with the number
+    /// of claims they overlap, in input order
+    pub fn k_overlap(&self, k: usize) -> Vec<(&Claim, usize)> {
+        self.claims
+            .iter()
+            .map(|c| (c, self.intersecting(&c.rect).len().saturating_sub(1)))
+            .filter(|&(_, n)| n >= k)
+            .collect()
+    }
+}
+
+#[test]
+fn test_index() -> Result<()> {
+    let claims = parser::parse(EXAMPLE.as_bytes())?;
+    let index = ClaimIndex::new(claims.clone());
+    assert_eq!(index.at((3, 3)), vec![&claims[0], &claims[1]]);
+    assert_eq!(index.at((6, 6)), vec![&claims[2]]);
+    assert!(index.at((0, 0)).is_empty());
+    assert_eq!(
+        index.intersecting(&Rect::new((4, 4), (2, 2))),
+        vec![&claims[0], &claims[1], &claims[2]]
+    );
+    assert_eq!(index.k_overlap(1), vec![(&claims[0], 1), (&claims[1], 1)]);
+    assert_eq!(index.k_overlap(0).len(), 3);
+    assert!(ClaimIndex::new(vec![]).at((0, 0)).is_empty());
+    // Compare with a linear scan, with enough claims for a few levels
+    let mut rng = testrand::Lcg::new(testrand::SEED);
+    let mut random = |max: u64| rng.below(max) as i64;
+    let claims = (0..700)
+        .map(|id| Claim {
+            id,
+            rect: Rect::new((random(1000), random(1000)), (random(50), random(50))),
+        })
+        .collect::<Vec<_>>();
+    let index = ClaimIndex::new(claims.clone());
+    testrand::differential(
+        100,
+        |rng| {
+            let mut random = |max: u64| rng.below(max) as i64;
+            let pos = (random(1000), random(1000));
+            let rect = Rect::new((random(1000), random(1000)), (random(100), random(100)));
+            (pos, rect)
+        },
+        |&(pos, rect)| (index.at(pos), index.intersecting(&rect)),
+        |&(pos, rect)| {
+            (
+                claims.iter().filter(|c| c.rect.contains(pos)).collect(),
+                claims
+                    .iter()
+                    .filter(|c| c.rect.intersection(&rect).is_some())
+                    .collect(),
+            )
+        },
+    );
+    Ok(())
+}
+
+/// Claims linked to the ones they overlap
+#[derive(Debug, Clone)]
+pub struct ConflictGraph {
+    pub claims: Vec<Claim>,
+    /// For each claim, the indexes of the claims it overlaps and the
+    /// area of each overlap
+    pub overlaps: Vec<Vec<(usize, u64)>>,
+}
+
+impl ConflictGraph {
+    pub fn new(claims: Vec<Claim>) -> ConflictGraph {
+        let index = ClaimIndex::new(claims);
+        let overlaps = index
+            .claims
+            .iter()
+            .enumerate()
+            .map(|(i, claim)| {
+                let hit = |r: &Rect| r.intersection(&claim.rect).is_some();
+                index
+                    .search(hit, hit)
+                    .into_iter()
+                    .filter(|&j| j != i)
+                    .map(|j| {
+                        let area = index.claims[j]
+                            .rect
+                            .intersection(&claim.rect)
+                            .map_or(0, |r| r.area());
+                        (j, area)
+                    })
+                    .collect()
+            })
+            .collect();
+        ConflictGraph {
+            claims: index.claims,
+            overlaps,
+        }
+    }
+
+    /// Groups of claims connected by overlaps, as indexes, leaving out
+    /// the isolated claims
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut component = vec![None; self.claims.len()];
+        let mut components = vec![];
+        for start in 0..self.claims.len() {
+            if component[start].is_some() || self.overlaps[start].is_empty() {
+                continue;
+            }
+            let mut members = vec![];
+            let mut stack = vec![start];
+            component[start] = Some(components.len());
+            while let Some(i) = stack.pop() {
+                members.push(i);
+                for &(j, _) in &self.overlaps[i] {
+                    if component[j].is_none() {
+                        component[j] = Some(components.len());
+                        stack.push(j);
+                    }
+                }
+            }
+            members.sort_unstable();
+            components.push(members);
+        }
+        components
+    }
+
+    /// Claims that don't overlap any other
+    pub fn isolated(&self) -> Vec<&Claim> {
+        self.claims
+            .iter()
+            .zip(&self.overlaps)
+            .filter(|(_, o)| o.is_empty())
+            .map(|(c, _)| c)
+            .collect()
+    }
+
+    /// Graphviz representation, with the overlapping areas as labels
+    pub fn dot(&self) -> String {
+        let mut dot = String::from("graph claims {\n");
+        for c in &self.claims {
+            dot.push_str(&format!("  \"#{}\";\n", c.id));
+        }
+        for (i, overlaps) in self.overlaps.iter().enumerate() {
+            for &(j, area) in overlaps.iter().filter(|&&(j, _)| i < j) {
+                dot.push_str(&format!(
+                    "  \"#{}\" -- \"#{}\" [label=\"{}\"];\n",
+                    self.claims[i].id, self.claims[j].id, area
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl fmt::Display for ConflictGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (claim, overlaps) in self.claims.iter().zip(&self.overlaps) {
+            write!(f, "#{}:", claim.id)?;
+            if overlaps.is_empty() {
+                write!(f, " isolated")?;
+            }
+            for &(j, area) in overlaps {
+                write!(f, " #{} ({})", self.claims[j].id, area)?;
+            }
+            writeln!(f)?;
+        }
+        for (n, component) in self.components().iter().enumerate() {
+            write!(f, "component {}:", n + 1)?;
+            for &i in component {
+                write!(f, " #{}", self.claims[i].id)?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "isolated:")?;
+        for c in self.isolated() {
+            write!(f, " #{}", c.id)?;
+        }
+        writeln!(f)
+    }
+}
+
+#[test]
+fn test_graph() -> Result<()> {
+    let claims = parser::parse(EXAMPLE.as_bytes())?;
+    let graph = ConflictGraph::new(claims.clone());
+    assert_eq!(graph.overlaps, vec![vec![(1, 4)], vec![(0, 4)], vec![]]);
+    assert_eq!(graph.components(), vec![vec![0, 1]]);
+    assert_eq!(graph.isolated(), vec![&claims[2]]);
+    assert_eq!(
+        graph.to_string(),
+        "#1: #2 (4)\n#2: #1 (4)\n#3: isolated\ncomponent 1: #1 #2\nisolated: #3\n"
+    );
+    assert_eq!(
+        graph.dot(),
+        "graph claims {\n  \"#1\";\n  \"#2\";\n  \"#3\";\n  \"#1\" -- \"#2\" [label=\"4\"];\n}\n"
+    );
+    // A chain of overlaps is a single component
+    let claims = parser::parse("#1 @ 0,0: 2x2\n#2 @ 5,5: 2x2\n#3 @ 1,1: 5x5\n".as_bytes())?;
+    assert_eq!(ConflictGraph::new(claims).components(), vec![vec![0, 1, 2]]);
+    Ok(())
+}
+
+pub fn process_a(bufin: impl BufRead) -> Result<usize> {
+    let claims = parser::parse(bufin)?;
+    Ok(overlap_area(&claims) as usize)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    assert_eq!(process_a(EXAMPLE.as_bytes())?, 4);
+    Ok(())
+}
+
+pub fn process_b(bufin: impl BufRead) -> Result<usize> {
+    let claims = parser::parse(bufin)?;
+    non_overlapping(&claims)
+        .first()
+        .map(|c| c.id)
+        .ok_or_else(|| anyhow!("non-overlapping id not found"))
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    assert_eq!(process_b(EXAMPLE.as_bytes())?, 3);
     Ok(())
 }
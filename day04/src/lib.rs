@@ -2,15 +2,22 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-#[cfg(test)]
+use anyhow::anyhow;
 use anyhow::Result;
+use chrono::Duration;
 use chrono::NaiveDate;
 use chrono::NaiveDateTime;
+use chrono::NaiveTime;
+use chrono::Timelike;
 use std::cmp;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::BufRead;
+use std::ops::Range;
 
 pub type Guard = usize;
 
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub enum Event {
     #[default]
     Sleep,
@@ -18,7 +25,7 @@ pub enum Event {
     Wakes,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Entry {
     pub when: NaiveDateTime,
     pub event: Event,
@@ -57,6 +64,31 @@ impl Entry {
     }
 }
 
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Sleep => write!(f, "falls asleep"),
+            Event::Begin(g) => write!(f, "Guard #{} begins shift", g),
+            Event::Wakes => write!(f, "wakes up"),
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.when.second() == 0 {
+            write!(f, "[{}] {}", self.when.format("%Y-%m-%d %H:%M"), self.event)
+        } else {
+            write!(
+                f,
+                "[{}] {}",
+                self.when.format("%Y-%m-%d %H:%M:%S"),
+                self.event
+            )
+        }
+    }
+}
+
 pub const EXAMPLE: &str = "[1518-11-01 00:00] Guard #10 begins shift
 [1518-11-01 00:05] falls asleep
 [1518-11-01 00:25] wakes up
@@ -78,15 +110,20 @@ pub const EXAMPLE: &str = "[1518-11-01 00:00] Guard #10 begins shift
 
 pub mod parser {
     use anyhow::anyhow;
+    use anyhow::Context;
     use anyhow::Result;
     use chrono::NaiveDate;
+    use chrono::NaiveDateTime;
     use nom::branch;
     use nom::bytes::complete as bytes;
     use nom::character::complete as character;
     use nom::combinator;
-    use nom::multi;
+    use nom::error;
+    use nom::sequence;
     use nom::IResult;
+    use std::fs;
     use std::io::BufRead;
+    use std::path::Path;
 
     use super::Entry;
     use super::Event;
@@ -111,8 +148,10 @@ pub mod parser {
         branch::alt((sleep, branch::alt((wakes, begin))))(input)
     }
 
-    pub fn line(input: &str) -> IResult<&str, Entry> {
-        let (input, _) = bytes::tag("[")(input)?;
+    /// Date and time, with optional seconds; fails without
+    /// backtracking if they are out of range
+    pub fn datetime(input: &str) -> IResult<&str, NaiveDateTime> {
+        let start = input;
         let (input, year) = character::i32(input)?;
         let (input, _) = bytes::tag("-")(input)?;
         let (input, month) = character::u32(input)?;
@@ -122,25 +161,62 @@ pub mod parser {
         let (input, hour) = character::u32(input)?;
         let (input, _) = bytes::tag(":")(input)?;
         let (input, min) = character::u32(input)?;
+        let (input, sec) =
+            combinator::opt(sequence::preceded(bytes::tag(":"), character::u32))(input)?;
+        let dt = NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|d| d.and_hms_opt(hour, min, sec.unwrap_or(0)))
+            .ok_or_else(|| nom::Err::Failure(error::Error::new(start, error::ErrorKind::Verify)))?;
+        Ok((input, dt))
+    }
+
+    /// A log line, without the line terminator
+    pub fn line(input: &str) -> IResult<&str, Entry> {
+        let (input, _) = bytes::tag("[")(input)?;
+        let (input, dt) = datetime(input)?;
         let (input, _) = bytes::tag("]")(input)?;
         let (input, _) = character::space1(input)?;
-        let dt = NaiveDate::from_ymd_opt(year, month, day)
-            .unwrap()
-            .and_hms_opt(hour, min, 0)
-            .unwrap();
         let (input, ev) = event(input)?;
-        let (input, _) = character::newline(input)?;
         let e = Entry::new(dt, ev);
         Ok((input, e))
     }
 
+    /// Parses the entries of a log, in the order they appear
     pub fn parse(mut bufin: impl BufRead) -> Result<Vec<Entry>> {
         let mut input = String::default();
         bufin.read_to_string(&mut input)?;
-        let result = combinator::all_consuming(multi::many1(line))(&input);
-        Ok(result
-            .map_err(|e| anyhow!("error reading input: {:?}", e))?
-            .1)
+        let entries = input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| {
+                combinator::all_consuming(line)(l)
+                    .map(|(_, entry)| entry)
+                    .map_err(|e| match e {
+                        nom::Err::Failure(e) if e.code == error::ErrorKind::Verify => {
+                            anyhow!("line {}: invalid date or time in {:?}", i + 1, l)
+                        }
+                        _ => anyhow!("line {}: invalid entry {:?}", i + 1, l),
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if entries.is_empty() {
+            return Err(anyhow!("log has no entries"));
+        }
+        Ok(entries)
+    }
+
+    /// Parses and merges the entries of several logs, in chronological
+    /// order; entries with the same time keep the order of the files
+    pub fn parse_files<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> Result<Vec<Entry>> {
+        let mut entries = vec![];
+        for path in paths {
+            let path = path.as_ref();
+            let file = fs::File::open(path).with_context(|| format!("{}", path.display()))?;
+            let mut file_entries = parse(std::io::BufReader::new(file))
+                .with_context(|| format!("{}", path.display()))?;
+            entries.append(&mut file_entries);
+        }
+        entries.sort();
+        Ok(entries)
     }
 }
 
@@ -174,5 +250,797 @@ fn test() -> Result<()> {
         Entry::new(d(5, 0, 55), Event::Wakes),
     ];
     assert_eq!(dat, ans);
+    let lines = dat.iter().map(|e| format!("{}\n", e)).collect::<String>();
+    assert_eq!(lines, EXAMPLE);
+    assert!(parser::parse("[1518-02-30 00:00] falls asleep\n".as_bytes()).is_err());
+    assert!(parser::parse("[1518-11-01 24:00] falls asleep\n".as_bytes()).is_err());
+    let err = parser::parse(
+        "[1518-11-01 00:00] Guard #10 begins shift\n[1518-13-40 25:99] falls asleep\n".as_bytes(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 2: invalid date or time in \"[1518-13-40 25:99] falls asleep\""
+    );
+    let err = parser::parse("[1518-11-01 00:00] dozes off\n".as_bytes()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 1: invalid entry \"[1518-11-01 00:00] dozes off\""
+    );
+    assert!(parser::parse("".as_bytes()).is_err());
+    let dat = parser::parse("[1518-11-01 00:05:30] falls asleep\n".as_bytes())?;
+    assert_eq!(dat[0].when, d(1, 0, 5) + Duration::seconds(30));
+    assert_eq!(dat[0].to_string(), "[1518-11-01 00:05:30] falls asleep");
+    Ok(())
+}
+
+#[test]
+fn test_parse_files() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("day04-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    // Split the example in two unsorted files, odd and even lines
+    let (odd, even): (Vec<_>, Vec<_>) = EXAMPLE.lines().enumerate().partition(|(i, _)| i % 2 == 1);
+    let write = |name: &str, lines: Vec<(usize, &str)>| -> Result<std::path::PathBuf> {
+        let path = dir.join(name);
+        let text = lines
+            .into_iter()
+            .rev()
+            .map(|(_, l)| format!("{}\n", l))
+            .collect::<String>();
+        std::fs::write(&path, text)?;
+        Ok(path)
+    };
+    let paths = vec![write("odd.log", odd)?, write("even.log", even)?];
+    let merged = parser::parse_files(&paths)?;
+    assert_eq!(merged, parser::parse(EXAMPLE.as_bytes())?);
+    assert!(parser::parse_files([dir.join("missing.log")]).is_err());
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+/// A shift of a guard, with the intervals in which they were asleep
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Shift {
+    pub guard: Guard,
+    pub begin: NaiveDateTime,
+    pub sleeps: Vec<Range<NaiveDateTime>>,
+}
+
+impl Shift {
+    /// Date of the midnight hour watched in the shift, the next day
+    /// for shifts that begin in the evening
+    pub fn date(&self) -> NaiveDate {
+        if self.begin.hour() >= 12 {
+            self.begin.date() + Duration::days(1)
+        } else {
+            self.begin.date()
+        }
+    }
+
+    /// Each minute in which the guard was asleep
+    pub fn asleep(&self) -> impl Iterator<Item = NaiveDateTime> + '_ {
+        self.sleeps.iter().flat_map(|sleep| {
+            let mut now = sleep.start;
+            std::iter::from_fn(move || {
+                (now < sleep.end).then(|| {
+                    let minute = now;
+                    now += Duration::minutes(1);
+                    minute
+                })
+            })
+        })
+    }
+}
+
+/// Inconsistencies found while grouping entries into shifts
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ShiftError {
+    /// Sleep or wake event before any guard begins a shift
+    NoGuard(NaiveDateTime),
+    /// Guard falls asleep while already asleep
+    OverlappingSleep {
+        asleep: NaiveDateTime,
+        again: NaiveDateTime,
+    },
+    /// Guard wakes up while not asleep
+    WakeWithoutSleep(NaiveDateTime),
+    /// Guard still asleep when the next shift begins, or when the log ends
+    UnterminatedSleep {
+        asleep: NaiveDateTime,
+        change: Option<NaiveDateTime>,
+    },
+}
+
+impl fmt::Display for ShiftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShiftError::NoGuard(when) => write!(f, "event before the first guard at {}", when),
+            ShiftError::OverlappingSleep { asleep, again } => write!(
+                f,
+                "guard asleep since {} falls asleep again at {}",
+                asleep, again
+            ),
+            ShiftError::WakeWithoutSleep(when) => {
+                write!(f, "guard wakes up without sleeping at {}", when)
+            }
+            ShiftError::UnterminatedSleep {
+                asleep,
+                change: Some(change),
+            } => write!(
+                f,
+                "guard asleep since {} is still asleep at shift change at {}",
+                asleep, change
+            ),
+            ShiftError::UnterminatedSleep {
+                asleep,
+                change: None,
+            } => write!(
+                f,
+                "guard asleep since {} is still asleep at the end of the log",
+                asleep
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ShiftError {}
+
+/// The entries of the log grouped into shifts, in chronological order
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ShiftLog {
+    shifts: Vec<Shift>,
+}
+
+impl ShiftLog {
+    pub fn new(mut entries: Vec<Entry>) -> Result<ShiftLog, ShiftError> {
+        entries.sort();
+        let mut shifts = Vec::<Shift>::new();
+        let mut since: Option<NaiveDateTime> = None;
+        for entry in entries {
+            match entry.event {
+                Event::Begin(guard) => {
+                    if let Some(asleep) = since {
+                        return Err(ShiftError::UnterminatedSleep {
+                            asleep,
+                            change: Some(entry.when),
+                        });
+                    }
+                    shifts.push(Shift {
+                        guard,
+                        begin: entry.when,
+                        sleeps: vec![],
+                    });
+                }
+                Event::Sleep => {
+                    if shifts.is_empty() {
+                        return Err(ShiftError::NoGuard(entry.when));
+                    }
+                    if let Some(asleep) = since {
+                        return Err(ShiftError::OverlappingSleep {
+                            asleep,
+                            again: entry.when,
+                        });
+                    }
+                    since = Some(entry.when);
+                }
+                Event::Wakes => {
+                    let shift = shifts.last_mut().ok_or(ShiftError::NoGuard(entry.when))?;
+                    let asleep = since
+                        .take()
+                        .ok_or(ShiftError::WakeWithoutSleep(entry.when))?;
+                    shift.sleeps.push(asleep..entry.when);
+                }
+            }
+        }
+        if let Some(asleep) = since {
+            return Err(ShiftError::UnterminatedSleep {
+                asleep,
+                change: None,
+            });
+        }
+        Ok(ShiftLog { shifts })
+    }
+
+    pub fn shifts(&self) -> &[Shift] {
+        &self.shifts
+    }
+}
+
+#[test]
+fn test_shiftlog() -> Result<()> {
+    let log = ShiftLog::new(parser::parse(EXAMPLE.as_bytes())?)?;
+    let guards = log.shifts().iter().map(|s| s.guard).collect::<Vec<_>>();
+    assert_eq!(guards, vec![10, 99, 10, 99, 99]);
+    assert_eq!(log.shifts()[0].sleeps.len(), 2);
+    assert_eq!(log.shifts()[0].asleep().count(), 45);
+    let t = |d, h, m| {
+        NaiveDate::from_ymd_opt(1518, 11, d)
+            .unwrap()
+            .and_hms_opt(h, m, 0)
+            .unwrap()
+    };
+    let check = |input: &str| ShiftLog::new(parser::parse(input.as_bytes()).unwrap());
+    assert_eq!(
+        check("[1518-11-01 00:05] falls asleep\n[1518-11-01 00:10] Guard #10 begins shift\n"),
+        Err(ShiftError::NoGuard(t(1, 0, 5)))
+    );
+    assert_eq!(
+        check("[1518-11-01 00:00] wakes up\n"),
+        Err(ShiftError::NoGuard(t(1, 0, 0)))
+    );
+    assert_eq!(
+        check(
+            "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:07] falls asleep
+"
+        ),
+        Err(ShiftError::OverlappingSleep {
+            asleep: t(1, 0, 5),
+            again: t(1, 0, 7)
+        })
+    );
+    assert_eq!(
+        check("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] wakes up\n"),
+        Err(ShiftError::WakeWithoutSleep(t(1, 0, 5)))
+    );
+    assert_eq!(
+        check(
+            "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-02 00:00] Guard #99 begins shift
+"
+        ),
+        Err(ShiftError::UnterminatedSleep {
+            asleep: t(1, 0, 5),
+            change: Some(t(2, 0, 0))
+        })
+    );
+    assert_eq!(
+        check("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n"),
+        Err(ShiftError::UnterminatedSleep {
+            asleep: t(1, 0, 5),
+            change: None
+        })
+    );
+    Ok(())
+}
+
+const DAY_MINUTES: u32 = 24 * 60;
+
+/// Time of the day in which the sleep of the guards is observed,
+/// minute by minute
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Window {
+    /// Minutes since midnight
+    start: u32,
+    minutes: u32,
+}
+
+impl Default for Window {
+    /// The midnight hour, as in the puzzle
+    fn default() -> Window {
+        Window {
+            start: 0,
+            minutes: 60,
+        }
+    }
+}
+
+impl Window {
+    /// From start, inclusive, to end, exclusive, wrapping around
+    /// midnight; the full day if they are the same. Seconds are
+    /// ignored.
+    pub fn new(start: NaiveTime, end: NaiveTime) -> Window {
+        let start = start.num_seconds_from_midnight() / 60;
+        let end = end.num_seconds_from_midnight() / 60;
+        let minutes = (end + DAY_MINUTES - start - 1) % DAY_MINUTES + 1;
+        Window { start, minutes }
+    }
+
+    pub fn full_day() -> Window {
+        Window {
+            start: 0,
+            minutes: DAY_MINUTES,
+        }
+    }
+
+    pub fn minutes(&self) -> usize {
+        self.minutes as usize
+    }
+
+    /// Position of the minute of the time of day in the window
+    pub fn index(&self, t: NaiveTime) -> Option<usize> {
+        let minute = t.num_seconds_from_midnight() / 60;
+        let offset = (minute + DAY_MINUTES - self.start) % DAY_MINUTES;
+        (offset < self.minutes).then_some(offset as usize)
+    }
+
+    /// Time of day of the position in the window
+    pub fn time(&self, index: usize) -> NaiveTime {
+        NaiveTime::MIN + Duration::minutes((self.start as usize + index) as i64)
+    }
+}
+
+impl fmt::Display for Window {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.time(0).format("%H:%M"),
+            self.time(self.minutes()).format("%H:%M")
+        )
+    }
+}
+
+impl std::str::FromStr for Window {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Window> {
+        if s == "24h" {
+            return Ok(Window::full_day());
+        }
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| anyhow!("invalid window {:?}, expected HH:MM-HH:MM or 24h", s))?;
+        let time = |t: &str| {
+            NaiveTime::parse_from_str(t, "%H:%M")
+                .map_err(|e| anyhow!("invalid window {:?}: {}", s, e))
+        };
+        Ok(Window::new(time(start)?, time(end)?))
+    }
+}
+
+#[test]
+fn test_window() -> Result<()> {
+    let t = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+    let midnight = Window::default();
+    assert_eq!(midnight, "00:00-01:00".parse()?);
+    assert_eq!(midnight.index(t(0, 24)), Some(24));
+    assert_eq!(midnight.index(t(1, 24)), None);
+    assert_eq!(midnight.index(t(23, 59)), None);
+    let late = "23:00-01:00".parse::<Window>()?;
+    assert_eq!(late.minutes(), 120);
+    assert_eq!(late.index(t(23, 0)), Some(0));
+    assert_eq!(late.index(t(0, 30)), Some(90));
+    assert_eq!(late.index(t(1, 0)), None);
+    assert_eq!(late.time(90), t(0, 30));
+    assert_eq!(late.to_string(), "23:00-01:00");
+    let day = "24h".parse::<Window>()?;
+    assert_eq!("06:00-06:00".parse::<Window>()?.minutes(), 1440);
+    assert_eq!(day.minutes(), 1440);
+    assert_eq!(day.to_string(), "00:00-00:00");
+    assert!("23:00".parse::<Window>().is_err());
+    assert!("23:00-25:00".parse::<Window>().is_err());
+    Ok(())
+}
+
+/// Sleep of a single shift in a window, minute by minute
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Timeline {
+    pub date: NaiveDate,
+    pub guard: Guard,
+    pub asleep: Vec<bool>,
+}
+
+impl fmt::Display for Timeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}  {:<5}",
+            self.date.format("%m-%d"),
+            format!("#{}", self.guard)
+        )?;
+        for &asleep in &self.asleep {
+            write!(f, "{}", if asleep { '#' } else { '.' })?;
+        }
+        Ok(())
+    }
+}
+
+impl ShiftLog {
+    pub fn timelines(&self, window: &Window) -> Vec<Timeline> {
+        self.shifts
+            .iter()
+            .map(|shift| {
+                let mut asleep = vec![false; window.minutes()];
+                for minute in shift.asleep() {
+                    if let Some(i) = window.index(minute.time()) {
+                        asleep[i] = true;
+                    }
+                }
+                Timeline {
+                    date: shift.date(),
+                    guard: shift.guard,
+                    asleep,
+                }
+            })
+            .collect()
+    }
+
+    /// The timelines of all shifts, as drawn in the puzzle statement;
+    /// the hours are added to the header if the window spans more
+    /// than one
+    pub fn chart(&self, window: &Window) -> String {
+        let times = (0..window.minutes())
+            .map(|i| window.time(i))
+            .collect::<Vec<_>>();
+        let digits = |digit: fn(&NaiveTime) -> u32| {
+            times
+                .iter()
+                .map(|t| char::from_digit(digit(t), 10).unwrap_or('?'))
+                .collect::<String>()
+        };
+        let mut chart = String::new();
+        if times.iter().any(|t| t.hour() != window.time(0).hour()) {
+            chart.push_str(&format!(
+                "Date   ID   Time\n            {}\n            {}\n",
+                digits(|t| t.hour() / 10),
+                digits(|t| t.hour() % 10)
+            ));
+        } else {
+            chart.push_str("Date   ID   Minute\n");
+        }
+        chart.push_str(&format!(
+            "            {}\n            {}\n",
+            digits(|t| t.minute() / 10),
+            digits(|t| t.minute() % 10)
+        ));
+        for timeline in self.timelines(window) {
+            chart.push_str(&format!("{}\n", timeline));
+        }
+        chart
+    }
+}
+
+#[test]
+fn test_chart() -> Result<()> {
+    let log = ShiftLog::new(parser::parse(EXAMPLE.as_bytes())?)?;
+    let chart = log.chart(&Window::default());
+    let lines = chart.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[0], "Date   ID   Minute");
+    assert_eq!(
+        lines[3],
+        "11-01  #10  .....####################.....#########################....."
+    );
+    assert_eq!(
+        lines[4],
+        "11-02  #99  ........................................##########.........."
+    );
+    assert_eq!(
+        lines[7],
+        "11-05  #99  .............................................##########....."
+    );
+    let chart = log.chart(&"23:58-00:03".parse()?);
+    let lines = chart.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 10);
+    assert_eq!(lines[1], "            22000");
+    assert_eq!(lines[2], "            33000");
+    assert_eq!(lines[3], "            55000");
+    assert_eq!(lines[4], "            89012");
+    Ok(())
+}
+
+/// The minutes on which a guard was asleep the most times, in order
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MostSlept {
+    pub count: usize,
+    pub minutes: Vec<NaiveTime>,
+}
+
+impl MostSlept {
+    /// The earliest of the most slept minutes in the window
+    pub fn minute(&self) -> NaiveTime {
+        self.minutes[0]
+    }
+
+    pub fn is_tie(&self) -> bool {
+        self.minutes.len() > 1
+    }
+}
+
+/// Sleep of a guard across all their shifts
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GuardSleep {
+    pub guard: Guard,
+    pub shifts: usize,
+    pub window: Window,
+    /// Times the guard was asleep on each minute of the window
+    pub histogram: Vec<usize>,
+}
+
+impl GuardSleep {
+    /// Total minutes asleep in the window
+    pub fn total(&self) -> usize {
+        self.histogram.iter().sum()
+    }
+
+    /// None if the guard never sleeps in the window
+    pub fn most_slept(&self) -> Option<MostSlept> {
+        let count = *self.histogram.iter().max()?;
+        if count == 0 {
+            return None;
+        }
+        let minutes = (0..self.histogram.len())
+            .filter(|&i| self.histogram[i] == count)
+            .map(|i| self.window.time(i))
+            .collect();
+        Some(MostSlept { count, minutes })
+    }
+}
+
+/// Guard and minute picked by a strategy
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Choice {
+    pub guard: Guard,
+    pub most_slept: MostSlept,
+}
+
+impl Choice {
+    /// The puzzle answer: guard ID times the minute
+    pub fn answer(&self) -> Result<u32> {
+        (self.guard as u32)
+            .checked_mul(self.most_slept.minute().minute())
+            .ok_or_else(|| anyhow!("answer overflow"))
+    }
+}
+
+/// Sleep statistics of each guard in a window, ordered by guard ID
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SleepStats {
+    pub window: Window,
+    pub guards: Vec<GuardSleep>,
+}
+
+impl SleepStats {
+    pub fn new(log: &ShiftLog, window: &Window) -> SleepStats {
+        let mut guards = BTreeMap::<Guard, GuardSleep>::new();
+        for shift in log.shifts() {
+            let g = guards.entry(shift.guard).or_insert_with(|| GuardSleep {
+                guard: shift.guard,
+                shifts: 0,
+                window: *window,
+                histogram: vec![0; window.minutes()],
+            });
+            g.shifts += 1;
+            for minute in shift.asleep() {
+                if let Some(i) = window.index(minute.time()) {
+                    g.histogram[i] += 1;
+                }
+            }
+        }
+        SleepStats {
+            window: *window,
+            guards: guards.into_values().collect(),
+        }
+    }
+
+    pub fn get(&self, guard: Guard) -> Option<&GuardSleep> {
+        self.guards
+            .binary_search_by_key(&guard, |g| g.guard)
+            .ok()
+            .map(|i| &self.guards[i])
+    }
+
+    /// Picks the sleeping guard with the greatest key, the one with
+    /// the lowest ID among ties, along with their most slept minute
+    pub fn choose<K: Ord>(&self, key: impl Fn(&GuardSleep, &MostSlept) -> K) -> Option<Choice> {
+        self.guards
+            .iter()
+            .filter_map(|g| g.most_slept().map(|most_slept| (g, most_slept)))
+            .rev()
+            .max_by_key(|(g, most_slept)| key(g, most_slept))
+            .map(|(g, most_slept)| Choice {
+                guard: g.guard,
+                most_slept,
+            })
+    }
+
+    /// Guard-by-minute matrix of the times asleep
+    pub fn csv(&self) -> String {
+        let mut csv = String::from("guard");
+        for i in 0..self.window.minutes() {
+            csv.push_str(&format!(",{}", self.window.time(i).format("%H:%M")));
+        }
+        csv.push('\n');
+        for g in &self.guards {
+            csv.push_str(&format!("{}", g.guard));
+            for n in &g.histogram {
+                csv.push_str(&format!(",{}", n));
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Fraction compared without rounding
+#[derive(Debug, Clone, Copy)]
+struct Ratio(usize, usize);
+
+impl cmp::Ord for Ratio {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        (self.0 as u128 * other.1 as u128).cmp(&(other.0 as u128 * self.1 as u128))
+    }
+}
+
+impl cmp::PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl cmp::PartialEq for Ratio {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl cmp::Eq for Ratio {}
+
+/// Ways of picking the guard and minute to sneak in
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Strategy {
+    /// Guard with the most minutes asleep, at their most slept minute
+    MostAsleep,
+    /// Guard most frequently asleep on the same minute
+    MostFrequentMinute,
+    /// Guard asleep on the same minute in the largest share of their
+    /// shifts
+    MostReliableMinute,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [
+        Strategy::MostAsleep,
+        Strategy::MostFrequentMinute,
+        Strategy::MostReliableMinute,
+    ];
+
+    pub fn choose(self, stats: &SleepStats) -> Option<Choice> {
+        match self {
+            Strategy::MostAsleep => stats.choose(|g, _| g.total()),
+            Strategy::MostFrequentMinute => stats.choose(|_, most_slept| most_slept.count),
+            Strategy::MostReliableMinute => {
+                stats.choose(|g, most_slept| Ratio(most_slept.count, g.shifts))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::MostAsleep => write!(f, "most asleep"),
+            Strategy::MostFrequentMinute => write!(f, "most frequent minute"),
+            Strategy::MostReliableMinute => write!(f, "most reliable minute"),
+        }
+    }
+}
+
+#[test]
+fn test_stats() -> Result<()> {
+    let t = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+    let log = ShiftLog::new(parser::parse(EXAMPLE.as_bytes())?)?;
+    let stats = SleepStats::new(&log, &Window::default());
+    let g10 = stats.get(10).unwrap();
+    assert_eq!((g10.shifts, g10.total()), (2, 50));
+    assert_eq!(
+        g10.most_slept(),
+        Some(MostSlept {
+            count: 2,
+            minutes: vec![t(0, 24)]
+        })
+    );
+    let g99 = stats.get(99).unwrap();
+    assert_eq!((g99.shifts, g99.total()), (3, 30));
+    assert_eq!(g99.most_slept().unwrap().minute(), t(0, 45));
+    assert!(stats.get(11).is_none());
+    let csv = stats.csv();
+    assert_eq!(csv.lines().count(), 3);
+    assert!(csv.starts_with("guard,00:00,00:01,00:02,"));
+    assert!(csv.lines().nth(1).unwrap().starts_with("10,0,0,0,0,0,1,1,"));
+    let answers = Strategy::ALL
+        .iter()
+        .map(|s| s.choose(&stats).unwrap().answer())
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(answers, vec![240, 4455, 240]);
+    // Guard 1 sleeps on minutes 0-9 of their only shift, guard 2 on
+    // minutes 3 and 4 of both of theirs
+    let log = ShiftLog::new(parser::parse(
+        "[1518-11-01 00:00] Guard #1 begins shift
+[1518-11-01 00:00] falls asleep
+[1518-11-01 00:10] wakes up
+[1518-11-02 00:00] Guard #2 begins shift
+[1518-11-02 00:03] falls asleep
+[1518-11-02 00:05] wakes up
+[1518-11-03 00:00] Guard #2 begins shift
+[1518-11-03 00:03] falls asleep
+[1518-11-03 00:05] wakes up
+"
+        .as_bytes(),
+    )?)?;
+    let stats = SleepStats::new(&log, &Window::default());
+    let most_slept = stats.get(1).unwrap().most_slept().unwrap();
+    assert!(most_slept.is_tie());
+    assert_eq!(most_slept.minutes.len(), 10);
+    assert_eq!(most_slept.minute(), t(0, 0));
+    let choice = Strategy::MostReliableMinute.choose(&stats).unwrap();
+    assert_eq!(
+        (choice.guard, choice.most_slept.minutes),
+        (1, (0..10).map(|m| t(0, m)).collect())
+    );
+    let choice = Strategy::MostFrequentMinute.choose(&stats).unwrap();
+    assert_eq!(
+        (choice.guard, choice.most_slept.minutes),
+        (2, vec![t(0, 3), t(0, 4)])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_stats_window() -> Result<()> {
+    let t = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+    // Guard 1 naps before midnight and on minute 30 of the 1 o'clock
+    // hour, which the midnight hour does not see
+    let log = ShiftLog::new(parser::parse(
+        "[1518-11-01 23:00] Guard #1 begins shift
+[1518-11-01 23:30] falls asleep
+[1518-11-01 23:40] wakes up
+[1518-11-02 01:30] falls asleep
+[1518-11-02 01:31] wakes up
+[1518-11-02 23:50] Guard #2 begins shift
+[1518-11-03 00:30] falls asleep
+[1518-11-03 00:35] wakes up
+"
+        .as_bytes(),
+    )?)?;
+    let stats = SleepStats::new(&log, &Window::default());
+    assert!(stats.get(1).unwrap().most_slept().is_none());
+    assert_eq!(stats.get(2).unwrap().total(), 5);
+    let stats = SleepStats::new(&log, &"23:00-01:00".parse()?);
+    let g1 = stats.get(1).unwrap();
+    assert_eq!(g1.total(), 10);
+    assert_eq!(g1.most_slept().unwrap().minute(), t(23, 30));
+    let choice = Strategy::MostAsleep.choose(&stats).unwrap();
+    assert_eq!(choice.guard, 1);
+    let stats = SleepStats::new(&log, &Window::full_day());
+    assert_eq!(stats.get(1).unwrap().total(), 11);
+    assert_eq!(stats.get(1).unwrap().histogram[90], 1);
+    let timelines = log.timelines(&"23:00-01:00".parse()?);
+    assert_eq!(
+        timelines[0].date,
+        NaiveDate::from_ymd_opt(1518, 11, 2).unwrap()
+    );
+    assert_eq!(timelines[0].asleep.iter().filter(|&&a| a).count(), 10);
+    Ok(())
+}
+
+/// Puzzle answer of the strategy over the entries of a log
+pub fn answer(strategy: Strategy, entries: Vec<Entry>) -> Result<u32> {
+    let log = ShiftLog::new(entries)?;
+    strategy
+        .choose(&SleepStats::new(&log, &Window::default()))
+        .ok_or_else(|| anyhow!("no guard sleeps"))?
+        .answer()
+}
+
+pub fn process_a(bufin: impl BufRead) -> Result<u32> {
+    answer(Strategy::MostAsleep, parser::parse(bufin)?)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    assert_eq!(process_a(EXAMPLE.as_bytes())?, 240);
+    Ok(())
+}
+
+pub fn process_b(bufin: impl BufRead) -> Result<u32> {
+    answer(Strategy::MostFrequentMinute, parser::parse(bufin)?)
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    assert_eq!(process_b(EXAMPLE.as_bytes())?, 4455);
     Ok(())
 }
@@ -3,43 +3,11 @@
 // file 'LICENSE', which is part of this source code package.
 
 use anyhow::Result;
-use std::collections::VecDeque;
-use std::io::{stdin, BufRead};
+use std::io::stdin;
 
 use day05::*;
 
-fn process(bufin: impl BufRead) -> Result<usize> {
-    let input: VecDeque<Unit> = parser::parse(bufin)?.into();
-    let polymer = react(input);
-    Ok(polymer.len())
-}
-
-#[test]
-fn test() -> Result<()> {
-    assert_eq!(process(EXAMPLE.as_bytes())?, 10);
-    Ok(())
-}
-
-#[test]
-fn test_react() -> Result<()> {
-    let reacted = react(VecDeque::from(parser::parse(EXAMPLE.as_bytes())?));
-    let ans = VecDeque::from([
-        Unit::from('d'),
-        Unit::from('a'),
-        Unit::from('b'),
-        Unit::from('C'),
-        Unit::from('B'),
-        Unit::from('A'),
-        Unit::from('c'),
-        Unit::from('a'),
-        Unit::from('D'),
-        Unit::from('A'),
-    ]);
-    assert_eq!(reacted, ans);
-    Ok(())
-}
-
 fn main() -> Result<()> {
-    println!("{}", process(stdin().lock())?);
+    println!("{}", process_a(stdin().lock())?);
     Ok(())
 }
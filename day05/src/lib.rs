@@ -2,15 +2,21 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-#[cfg(test)]
+use anyhow::anyhow;
 use anyhow::Result;
-use std::collections::VecDeque;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
+use std::io::BufRead;
+
+use rayon::prelude::*;
 
 pub const EXAMPLE: &str = "dabAcCaCBAcCcaDA\n";
 
-type Polymer = VecDeque<Unit>;
+pub type Polymer = Vec<Unit>;
 
-#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
 pub struct Unit {
     pub typ: char,
     pub pol: bool,
@@ -34,15 +40,106 @@ impl From<(char, bool)> for Unit {
     }
 }
 
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pol {
+            write!(f, "{}", self.typ)
+        } else {
+            write!(f, "{}", self.typ.to_ascii_uppercase())
+        }
+    }
+}
+
+/// Lazy iterator over the units of a `BufRead`, a single line of
+/// letters optionally followed by whitespace; an input without any is
+/// an error
+pub struct Units<R> {
+    bytes: io::Bytes<R>,
+    offset: usize,
+    ended: bool,
+    empty: bool,
+}
+
+impl<R: BufRead> Iterator for Units<R> {
+    type Item = Result<Unit>;
+    fn next(&mut self) -> Option<Result<Unit>> {
+        loop {
+            let byte = match self.bytes.next() {
+                Some(Ok(byte)) => byte,
+                Some(Err(e)) => return Some(Err(e.into())),
+                None if self.empty => {
+                    // Reported once
+                    self.empty = false;
+                    return Some(Err(anyhow!("empty polymer")));
+                }
+                None => return None,
+            };
+            self.offset += 1;
+            let c = char::from(byte);
+            if c.is_ascii_whitespace() {
+                self.ended = true;
+            } else if self.ended {
+                return Some(Err(anyhow!(
+                    "offset {}: unit {:?} after the end of the polymer",
+                    self.offset - 1,
+                    c
+                )));
+            } else if c.is_ascii_alphabetic() {
+                self.empty = false;
+                return Some(Ok(Unit::from(c)));
+            } else {
+                return Some(Err(anyhow!(
+                    "offset {}: invalid unit {:?}",
+                    self.offset - 1,
+                    c
+                )));
+            }
+        }
+    }
+}
+
+pub fn units<R: BufRead>(bufin: R) -> Units<R> {
+    Units {
+        bytes: bufin.bytes(),
+        offset: 0,
+        ended: false,
+        empty: true,
+    }
+}
+
+#[test]
+fn test_units() -> Result<()> {
+    let units = units("aBc\n".as_bytes()).collect::<Result<Vec<_>>>()?;
+    assert_eq!(
+        units,
+        vec![Unit::from('a'), Unit::from('B'), Unit::from('c')]
+    );
+    assert_eq!(units[1].to_string(), "B");
+    let e = self::units("ab1".as_bytes()).collect::<Result<Vec<_>>>();
+    assert_eq!(e.unwrap_err().to_string(), "offset 2: invalid unit '1'");
+    let e = self::units("ab\ncd\n".as_bytes()).collect::<Result<Vec<_>>>();
+    assert_eq!(
+        e.unwrap_err().to_string(),
+        "offset 3: unit 'c' after the end of the polymer"
+    );
+    for empty in ["", "\n", " \n\n"] {
+        let e = self::units(empty.as_bytes()).collect::<Result<Vec<_>>>();
+        assert_eq!(e.unwrap_err().to_string(), "empty polymer");
+    }
+    Ok(())
+}
+
 pub mod parser {
     use anyhow::anyhow;
     use anyhow::Result;
+    use nom::bytes::complete as bytes;
     use nom::character::complete as character;
     use nom::combinator;
-    use nom::multi;
+    use nom::sequence;
     use nom::IResult;
     use std::io::BufRead;
 
+    use super::Rules;
     use super::Unit;
 
     pub fn unit(input: &str) -> IResult<&str, Unit> {
@@ -55,19 +152,42 @@ pub mod parser {
         })(input)
     }
 
-    pub fn line(input: &str) -> IResult<&str, Vec<Unit>> {
-        let (input, units) = multi::many1(unit)(input)?;
-        let (input, _) = character::newline(input)?;
-        Ok((input, units))
+    /// The whole polymer, with the same rules as the streaming
+    /// `units`
+    pub fn parse(bufin: impl BufRead) -> Result<Vec<Unit>> {
+        super::units(bufin).collect()
+    }
+
+    /// Two units that react and, optionally, the unit they produce:
+    /// `aB` or `aB -> c`
+    pub fn rule(input: &str) -> IResult<&str, (Unit, Unit, Option<Unit>)> {
+        let (input, u1) = unit(input)?;
+        let (input, u2) = unit(input)?;
+        let arrow = sequence::delimited(character::space0, bytes::tag("->"), character::space0);
+        let (input, product) = combinator::opt(sequence::preceded(arrow, unit))(input)?;
+        Ok((input, (u1, u2, product)))
     }
 
-    pub fn parse(mut bufin: impl BufRead) -> Result<Vec<Unit>> {
+    /// Parses a rule set: one rule per line, or `case` for the rule of
+    /// the puzzle; `#` starts a comment
+    pub fn rules(mut bufin: impl BufRead) -> Result<Rules> {
         let mut input = String::default();
         bufin.read_to_string(&mut input)?;
-        let result = combinator::all_consuming(line)(&input);
-        Ok(result
-            .map_err(|e| anyhow!("error reading input: {:?}", e))?
-            .1)
+        let mut rules = Rules::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line == "case" {
+                rules.case = true;
+                continue;
+            }
+            let (_, (u1, u2, product)) = combinator::all_consuming(rule)(line)
+                .map_err(|_| anyhow!("line {}: invalid rule {:?}", i + 1, line))?;
+            rules.add(u1, u2, product);
+        }
+        Ok(rules)
     }
 }
 
@@ -96,19 +216,464 @@ fn test() -> Result<()> {
     Ok(())
 }
 
-pub fn react(mut polymer: Polymer) -> Polymer {
-    if let Some(u1) = polymer.pop_front() {
-        let mut polymer2 = react(polymer);
-        if let Some(u2) = polymer2.pop_front() {
-            if u1.typ != u2.typ || u1.pol == u2.pol {
-                polymer2.push_front(u2);
-                polymer2.push_front(u1);
+/// Returns true if the units annihilate each other when adjacent
+pub fn reacts(u1: &Unit, u2: &Unit) -> bool {
+    u1.typ == u2.typ && u1.pol != u2.pol
+}
+
+/// Which adjacent unit pairs react, in either order, and the unit
+/// they produce, if any. A reaction never produces more than one unit,
+/// so that reacting always shortens the polymer.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Rules {
+    /// Units of the same type and opposite polarities annihilate each
+    /// other, as in the puzzle
+    pub case: bool,
+    pairs: BTreeMap<(Unit, Unit), Option<Unit>>,
+}
+
+/// The rule of the puzzle, used by default
+pub static CASE_RULES: Rules = Rules {
+    case: true,
+    pairs: BTreeMap::new(),
+};
+
+impl Default for Rules {
+    fn default() -> Rules {
+        CASE_RULES.clone()
+    }
+}
+
+impl Rules {
+    /// Rule set in which nothing reacts
+    pub fn new() -> Rules {
+        Rules {
+            case: false,
+            pairs: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a pair that reacts, taking precedence over the case rule
+    pub fn add(&mut self, u1: Unit, u2: Unit, product: Option<Unit>) {
+        self.pairs.insert((u1, u2), product);
+        self.pairs.insert((u2, u1), product);
+    }
+
+    /// None if the units don't react, otherwise what they produce
+    pub fn reaction(&self, u1: &Unit, u2: &Unit) -> Option<Option<Unit>> {
+        if let Some(product) = self.pairs.get(&(*u1, *u2)) {
+            return Some(*product);
+        }
+        (self.case && reacts(u1, u2)).then_some(None)
+    }
+}
+
+#[test]
+fn test_rules() -> Result<()> {
+    let u = Unit::from;
+    let rules = Rules::default();
+    assert_eq!(rules.reaction(&u('a'), &u('A')), Some(None));
+    assert_eq!(rules.reaction(&u('a'), &u('a')), None);
+    let rules = parser::rules(
+        "# ab makes c, c and D annihilate
+ab -> c
+cD
+
+"
+        .as_bytes(),
+    )?;
+    assert!(!rules.case);
+    assert_eq!(rules.reaction(&u('b'), &u('a')), Some(Some(u('c'))));
+    assert_eq!(rules.reaction(&u('D'), &u('c')), Some(None));
+    assert_eq!(rules.reaction(&u('a'), &u('A')), None);
+    assert_eq!(
+        react_with("xabDy".chars().map(u), &rules),
+        "xy".chars().map(u).collect::<Vec<_>>()
+    );
+    let rules = parser::rules("case\naA -> b\n".as_bytes())?;
+    assert_eq!(react_with("BaA".chars().map(u), &rules), vec![]);
+    assert_eq!(react_with("dD".chars().map(u), &rules), vec![]);
+    let e = parser::rules("ab\nabc\n".as_bytes()).unwrap_err();
+    assert_eq!(e.to_string(), "line 2: invalid rule \"abc\"");
+    assert!(parser::rules("ab -> cd\n".as_bytes()).is_err());
+    Ok(())
+}
+
+/// Receives the reactions of a reactor
+pub trait Tracer {
+    /// What the reactor keeps of each unit to identify it: nothing,
+    /// or its index in the input when tracing
+    type Index: Copy;
+
+    fn index(input_index: usize) -> Self::Index;
+
+    /// The units identified by left and right reacted
+    fn react(&mut self, left: Self::Index, right: Self::Index);
+}
+
+/// Tracer that keeps nothing, compiled away
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoTrace;
+
+impl Tracer for NoTrace {
+    type Index = ();
+
+    fn index(_: usize) {}
+
+    fn react(&mut self, _: (), _: ()) {}
+}
+
+/// Pair of units that reacted, by their indices in the input, and the
+/// step, counting reactions from 0, at which they did
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Reaction {
+    pub step: usize,
+    pub left: usize,
+    pub right: usize,
+}
+
+/// Tracer that records all reactions
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Trace {
+    pub reactions: Vec<Reaction>,
+}
+
+impl Tracer for Trace {
+    type Index = usize;
+
+    fn index(input_index: usize) -> usize {
+        input_index
+    }
+
+    fn react(&mut self, left: usize, right: usize) {
+        self.reactions.push(Reaction {
+            step: self.reactions.len(),
+            left,
+            right,
+        });
+    }
+}
+
+/// Reacts units as they arrive, keeping the irreducible polymer of
+/// the ones seen so far as a stack: each unit either reacts with the
+/// top or is pushed, and what a reaction produces is pushed in turn,
+/// so the whole reaction is O(n)
+#[derive(Debug, Clone)]
+pub struct Reactor<'r, T: Tracer = NoTrace> {
+    rules: &'r Rules,
+    polymer: Polymer,
+    indices: Vec<T::Index>,
+    pushed: usize,
+    tracer: T,
+}
+
+impl Default for Reactor<'static> {
+    fn default() -> Reactor<'static> {
+        Reactor::with_rules(&CASE_RULES)
+    }
+}
+
+impl Reactor<'static> {
+    pub fn new() -> Reactor<'static> {
+        Reactor::default()
+    }
+
+    /// Reacts all units of the reader, without loading it whole
+    pub fn from_reader(bufin: impl BufRead) -> Result<Reactor<'static>> {
+        let mut reactor = Reactor::new();
+        reactor.read(bufin)?;
+        Ok(reactor)
+    }
+}
+
+impl<'r> Reactor<'r> {
+    pub fn with_rules(rules: &'r Rules) -> Reactor<'r> {
+        Reactor::with_tracer(rules, NoTrace)
+    }
+
+    /// Appends an irreducible polymer. With the default rules, which
+    /// are confluent, the result is the same as reacting all units
+    /// in sequence, and only the units at the boundary react.
+    pub fn merge(mut self, other: Reactor) -> Reactor<'r> {
+        self.extend(other.polymer);
+        self
+    }
+}
+
+impl<'r, T: Tracer> Reactor<'r, T> {
+    pub fn with_tracer(rules: &'r Rules, tracer: T) -> Reactor<'r, T> {
+        Reactor {
+            rules,
+            polymer: Polymer::new(),
+            indices: vec![],
+            pushed: 0,
+            tracer,
+        }
+    }
+
+    pub fn push(&mut self, mut unit: Unit) {
+        let mut index = T::index(self.pushed);
+        self.pushed += 1;
+        while let Some(top) = self.polymer.last() {
+            match self.rules.reaction(top, &unit) {
+                None => break,
+                Some(product) => {
+                    self.polymer.pop();
+                    let top_index = self
+                        .indices
+                        .pop()
+                        .expect("indices in sync with the polymer");
+                    self.tracer.react(top_index, index);
+                    match product {
+                        Some(product) => {
+                            unit = product;
+                            index = top_index;
+                        }
+                        None => return,
+                    }
+                }
+            }
+        }
+        self.polymer.push(unit);
+        self.indices.push(index);
+    }
+
+    /// Reacts all units of the reader, without loading it whole; it
+    /// must have at least one
+    pub fn read(&mut self, bufin: impl BufRead) -> Result<()> {
+        for unit in units(bufin) {
+            self.push(unit?);
+        }
+        Ok(())
+    }
+
+    pub fn polymer(&self) -> &[Unit] {
+        &self.polymer
+    }
+
+    pub fn into_polymer(self) -> Polymer {
+        self.polymer
+    }
+
+    pub fn tracer(&self) -> &T {
+        &self.tracer
+    }
+
+    pub fn len(&self) -> usize {
+        self.polymer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.polymer.is_empty()
+    }
+}
+
+impl Reactor<'_, Trace> {
+    /// Input indices of the units of the polymer; a unit produced by a
+    /// reaction has the index of the left unit of its pair
+    pub fn survivors(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// The input with the surviving units marked with `^` below it
+    pub fn render(&self, input: &[Unit]) -> String {
+        let mut marks = vec!['-'; input.len()];
+        for &i in self.survivors() {
+            if let Some(mark) = marks.get_mut(i) {
+                *mark = '^';
             }
-            polymer2
-        } else {
-            VecDeque::from([u1])
         }
-    } else {
-        polymer
+        let units = input.iter().map(|u| u.to_string()).collect::<String>();
+        format!("{}\n{}\n", units, marks.into_iter().collect::<String>())
     }
 }
+
+#[test]
+fn test_trace() -> Result<()> {
+    let input = parser::parse(EXAMPLE.as_bytes())?;
+    let mut reactor = Reactor::with_tracer(&CASE_RULES, Trace::default());
+    reactor.extend(input.iter().copied());
+    assert_eq!(reactor.polymer(), react(input.iter().copied()));
+    let pairs = reactor
+        .tracer()
+        .reactions
+        .iter()
+        .map(|r| (r.step, r.left, r.right))
+        .collect::<Vec<_>>();
+    assert_eq!(pairs, vec![(0, 4, 5), (1, 3, 6), (2, 10, 11)]);
+    assert_eq!(
+        reactor.render(&input),
+        "dabAcCaCBAcCcaDA\n^^^----^^^--^^^^\n"
+    );
+    // A produced unit keeps the index of the left one of its pair
+    let rules = parser::rules("ab -> c\n".as_bytes())?;
+    let mut reactor = Reactor::with_tracer(&rules, Trace::default());
+    reactor.extend("xab".chars().map(Unit::from));
+    assert_eq!(reactor.survivors(), &[0, 1]);
+    assert_eq!(
+        reactor.render(&"xab".chars().map(Unit::from).collect::<Vec<_>>()),
+        "xab\n^^-\n"
+    );
+    Ok(())
+}
+
+impl<T: Tracer> Extend<Unit> for Reactor<'_, T> {
+    fn extend<I: IntoIterator<Item = Unit>>(&mut self, units: I) {
+        for unit in units {
+            self.push(unit);
+        }
+    }
+}
+
+impl<T: Tracer> fmt::Display for Reactor<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for unit in &self.polymer {
+            write!(f, "{}", unit)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn react(units: impl IntoIterator<Item = Unit>) -> Polymer {
+    react_with(units, &CASE_RULES)
+}
+
+pub fn react_with(units: impl IntoIterator<Item = Unit>, rules: &Rules) -> Polymer {
+    let mut reactor = Reactor::with_rules(rules);
+    reactor.extend(units);
+    reactor.into_polymer()
+}
+
+/// Units reacted by each task of `react_par`
+const CHUNK: usize = 1 << 14;
+
+fn react_chunked(units: &[Unit], chunk: usize) -> Polymer {
+    units
+        .par_chunks(chunk)
+        .map(|chunk| {
+            let mut reactor = Reactor::new();
+            reactor.extend(chunk.iter().copied());
+            reactor
+        })
+        .reduce(Reactor::new, Reactor::merge)
+        .into_polymer()
+}
+
+/// Reacts chunks of the polymer in parallel and merges their
+/// irreducible forms, which gives the same result as `react` as the
+/// reaction with the default rules is associative
+pub fn react_par(units: &[Unit]) -> Polymer {
+    react_chunked(units, CHUNK)
+}
+
+pub fn process_a(bufin: impl BufRead) -> Result<usize> {
+    let reactor = Reactor::from_reader(bufin)?;
+    Ok(reactor.len())
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    assert_eq!(process_a(EXAMPLE.as_bytes())?, 10);
+    Ok(())
+}
+
+#[test]
+fn test_react() -> Result<()> {
+    let reacted = react(parser::parse(EXAMPLE.as_bytes())?);
+    let ans = Vec::from([
+        Unit::from('d'),
+        Unit::from('a'),
+        Unit::from('b'),
+        Unit::from('C'),
+        Unit::from('B'),
+        Unit::from('A'),
+        Unit::from('c'),
+        Unit::from('a'),
+        Unit::from('D'),
+        Unit::from('A'),
+    ]);
+    assert_eq!(reacted, ans);
+    let reactor = Reactor::from_reader(EXAMPLE.as_bytes())?;
+    assert_eq!(reactor.polymer(), ans);
+    assert_eq!(reactor.to_string(), "dabCBAcaDA");
+    // Deep enough to overflow the stack of a recursive reactor
+    let deep = "a".repeat(500_000) + &"A".repeat(500_000);
+    assert!(Reactor::from_reader(deep.as_bytes())?.is_empty());
+    assert!(Reactor::from_reader("aA!".as_bytes()).is_err());
+    Ok(())
+}
+
+/// Finds the unit type whose removal leaves the shortest polymer,
+/// and its length, trying all types in parallel. Removing a type
+/// before or after reacting gives the same result, so the polymer is
+/// reacted only once and each type is removed from that.
+pub fn shortest_removal(units: &[Unit]) -> Option<(char, usize)> {
+    let reacted = react_par(units);
+    let typs = units.iter().map(|u| u.typ).collect::<HashSet<_>>();
+    typs.into_par_iter()
+        .map(|typ| {
+            let filtered = reacted.iter().filter(|u| u.typ != typ).copied();
+            (typ, react(filtered).len())
+        })
+        .min_by_key(|&(typ, len)| (len, typ))
+}
+
+#[cfg(test)]
+fn shortest_removal_naive(units: &[Unit]) -> Option<(char, usize)> {
+    let typs = units.iter().map(|u| u.typ).collect::<HashSet<_>>();
+    typs.into_iter()
+        .map(|typ| {
+            let filtered = units.iter().filter(|u| u.typ != typ).copied();
+            (typ, react(filtered).len())
+        })
+        .min_by_key(|&(typ, len)| (len, typ))
+}
+
+#[test]
+fn test_par() -> Result<()> {
+    let input = parser::parse(EXAMPLE.as_bytes())?;
+    assert_eq!(react_par(&input), react(input.iter().copied()));
+    assert_eq!(shortest_removal(&input), Some(('c', 4)));
+    assert_eq!(shortest_removal(&[]), None);
+    // Differential test against the sequential versions, with
+    // pseudo-random polymers of few types so that they react a lot
+    testrand::differential(
+        500,
+        |rng| {
+            let len = rng.below(200) as usize;
+            let typs = 1 + rng.below(4) as u8;
+            let units = (0..len)
+                .map(|_| {
+                    Unit::from((
+                        char::from(b'a' + rng.below(typs as u64) as u8),
+                        rng.below(2) == 0,
+                    ))
+                })
+                .collect::<Vec<_>>();
+            (units, 1 + rng.below(16) as usize)
+        },
+        |(units, chunk)| (react_chunked(units, *chunk), shortest_removal(units)),
+        |(units, _)| (react(units.iter().copied()), shortest_removal_naive(units)),
+    );
+    Ok(())
+}
+
+pub fn process_b(bufin: impl BufRead) -> Result<usize> {
+    let input = units(bufin).collect::<Result<Polymer>>()?;
+    let (_, len) = shortest_removal(&input).ok_or_else(|| anyhow!("error calculating best"))?;
+    Ok(len)
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    assert_eq!(process_b(EXAMPLE.as_bytes())?, 4);
+    // Both parts take the same inputs
+    let unterminated = EXAMPLE.trim_end();
+    assert_eq!(process_a(unterminated.as_bytes())?, 10);
+    assert_eq!(process_b(unterminated.as_bytes())?, 4);
+    for bad in ["", "\n", "dabA1\n", "ab\ncd\n"] {
+        assert!(process_a(bad.as_bytes()).is_err(), "{:?}", bad);
+        assert!(process_b(bad.as_bytes()).is_err(), "{:?}", bad);
+    }
+    Ok(())
+}
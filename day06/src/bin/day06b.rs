@@ -2,42 +2,20 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-// use anyhow::anyhow;
 use anyhow::Result;
-use std::io::{stdin, BufRead};
+use std::env;
+use std::io::stdin;
 
 use day06::*;
 
-fn process(maxdist: usize, bufin: impl BufRead) -> Result<usize> {
-    let input = parser::parse(bufin)?;
-    let xsum: usize = input.iter().map(|qa| qa.tuple().0 as usize).sum();
-    let ysum: usize = input.iter().map(|qa| qa.tuple().1 as usize).sum();
-    let len = input.len();
-    let center = Qa::try_from(((xsum / len) as u16, (ysum / len) as u16))?;
-    let mut ans = 1;
-    for front in Sqrid::bf_iter(sqrid::qaqr_eval, &center) {
-        let mut done = true;
-        for (qa, _) in front {
-            let dist: usize = input.iter().map(|center| Qa::manhattan(center, &qa)).sum();
-            if dist < maxdist {
-                ans += 1;
-                done = false;
-            }
-        }
-        if done {
-            break;
-        }
-    }
-    Ok(ans)
-}
-
-#[test]
-fn test() -> Result<()> {
-    assert_eq!(process(32, EXAMPLE.as_bytes())?, 16);
-    Ok(())
-}
-
 fn main() -> Result<()> {
-    println!("{}", process(10000, stdin().lock())?);
+    // The metric can be given as argument, manhattan by default
+    let metric = env::args().nth(1).unwrap_or_default();
+    if metric.is_empty() {
+        println!("{}", process_b(10000, stdin().lock())?);
+    } else {
+        let centers = parser::parse(stdin().lock())?;
+        println!("{}", safe_area(metric.parse()?, &centers, 10000));
+    }
     Ok(())
 }
@@ -2,11 +2,14 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-#[cfg(test)]
+use anyhow::anyhow;
 use anyhow::Result;
+use std::fmt;
+use std::io::BufRead;
+use std::iter;
+use std::str::FromStr;
 
-pub type Sqrid = sqrid::sqrid_create!(1000, 1000, false);
-pub type Qa = sqrid::qa_create!(Sqrid);
+pub type Pos = (i64, i64);
 
 pub const EXAMPLE: &str = "1, 1
 1, 6
@@ -26,32 +29,50 @@ pub mod parser {
     use nom::IResult;
     use std::io::BufRead;
 
-    use super::Qa;
+    use super::Pos;
 
-    pub fn u16tuple(input: &str) -> IResult<&str, (u16, u16)> {
-        let (input, x) = character::u16(input)?;
-        let (input, _) = bytes::tag(", ")(input)?;
-        let (input, y) = character::u16(input)?;
-        Ok((input, (x, y)))
+    pub fn coord(input: &str) -> IResult<&str, i64> {
+        character::i64(input)
     }
 
-    pub fn qa(input: &str) -> IResult<&str, Qa> {
-        combinator::map_res(u16tuple, Qa::try_from)(input)
+    pub fn pos(input: &str) -> IResult<&str, Pos> {
+        let (input, x) = coord(input)?;
+        let (input, _) = bytes::tag(",")(input)?;
+        let (input, _) = character::space0(input)?;
+        let (input, y) = coord(input)?;
+        Ok((input, (x, y)))
     }
 
-    pub fn line(input: &str) -> IResult<&str, Qa> {
-        let (input, qa) = qa(input)?;
+    pub fn line(input: &str) -> IResult<&str, Pos> {
+        let (input, pos) = pos(input)?;
         let (input, _) = character::newline(input)?;
-        Ok((input, qa))
+        Ok((input, pos))
     }
 
-    pub fn parse(mut bufin: impl BufRead) -> Result<Vec<Qa>> {
+    /// Centers with coordinates in the range of i32, far enough from
+    /// the limits of i64 for the squared distances between cells to
+    /// fit in i128; an error for the ones outside of it
+    pub fn parse(mut bufin: impl BufRead) -> Result<Vec<Pos>> {
         let mut input = String::default();
         bufin.read_to_string(&mut input)?;
         let result = combinator::all_consuming(multi::many1(line))(&input);
-        Ok(result
+        let centers = result
             .map_err(|e| anyhow!("error reading input: {:?}", e))?
-            .1)
+            .1;
+        for (i, c) in centers.iter().enumerate() {
+            for coord in [c.0, c.1] {
+                if i32::try_from(coord).is_err() {
+                    return Err(anyhow!(
+                        "line {}: coordinate {} out of range, the limits are {} and {}",
+                        i + 1,
+                        coord,
+                        i32::MIN,
+                        i32::MAX
+                    ));
+                }
+            }
+        }
+        Ok(centers)
     }
 }
 
@@ -59,14 +80,932 @@ pub mod parser {
 fn test() -> Result<()> {
     assert_eq!(
         parser::parse(EXAMPLE.as_bytes())?,
-        &[
-            Qa::new_static::<1, 1>(),
-            Qa::new_static::<1, 6>(),
-            Qa::new_static::<8, 3>(),
-            Qa::new_static::<3, 4>(),
-            Qa::new_static::<5, 5>(),
-            Qa::new_static::<8, 9>(),
-        ]
+        &[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]
+    );
+    assert_eq!(
+        parser::parse("-5, 1000000\n3,-2\n".as_bytes())?,
+        &[(-5, 1_000_000), (3, -2)]
+    );
+    assert!(parser::parse("1 1\n".as_bytes()).is_err());
+    assert_eq!(
+        parser::parse("1, 1\n0, -2147483649\n".as_bytes())
+            .unwrap_err()
+            .to_string(),
+        "line 2: coordinate -2147483649 out of range, the limits are -2147483648 and 2147483647"
+    );
+    assert!(parser::parse("3000000000, 0\n".as_bytes()).is_err());
+    assert!(parser::parse("99999999999999999999, 0\n".as_bytes()).is_err());
+    Ok(())
+}
+
+/// Distance used to assign cells to their nearest center
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Metric {
+    #[default]
+    Manhattan,
+    Chebyshev,
+    SquaredEuclidean,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 3] = [
+        Metric::Manhattan,
+        Metric::Chebyshev,
+        Metric::SquaredEuclidean,
+    ];
+
+    /// Distance between the cells, in i128 so that it doesn't overflow
+    /// for coordinates in the range of i32 and well beyond
+    pub fn distance(self, a: Pos, b: Pos) -> i128 {
+        let dx = (i128::from(a.0) - i128::from(b.0)).abs();
+        let dy = (i128::from(a.1) - i128::from(b.1)).abs();
+        match self {
+            Metric::Manhattan => dx + dy,
+            Metric::Chebyshev => dx.max(dy),
+            Metric::SquaredEuclidean => dx * dx + dy * dy,
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Metric> {
+        match s {
+            "manhattan" => Ok(Metric::Manhattan),
+            "chebyshev" => Ok(Metric::Chebyshev),
+            "squared-euclidean" => Ok(Metric::SquaredEuclidean),
+            _ => Err(anyhow!(
+                "invalid metric {:?}, expected manhattan, chebyshev or squared-euclidean",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Metric::Manhattan => write!(f, "manhattan"),
+            Metric::Chebyshev => write!(f, "chebyshev"),
+            Metric::SquaredEuclidean => write!(f, "squared-euclidean"),
+        }
+    }
+}
+
+/// Index of the center nearest to the position, None if there is a
+/// tie
+pub fn nearest(metric: Metric, centers: &[Pos], pos: Pos) -> Option<usize> {
+    let mut best = (i128::MAX, None);
+    for (i, &c) in centers.iter().enumerate() {
+        let d = metric.distance(c, pos);
+        if d < best.0 {
+            best = (d, Some(i));
+        } else if d == best.0 {
+            best.1 = None;
+        }
+    }
+    best.1
+}
+
+/// Smallest box with all centers, as ((xmin, ymin), (xmax, ymax))
+pub fn bounding_box(centers: &[Pos]) -> Option<(Pos, Pos)> {
+    let xmin = centers.iter().map(|p| p.0).min()?;
+    let xmax = centers.iter().map(|p| p.0).max()?;
+    let ymin = centers.iter().map(|p| p.1).min()?;
+    let ymax = centers.iter().map(|p| p.1).max()?;
+    Some(((xmin, ymin), (xmax, ymax)))
+}
+
+/// Positions of the box, row by row
+fn box_iter(min: Pos, max: Pos) -> impl Iterator<Item = Pos> {
+    (min.1..=max.1).flat_map(move |y| (min.0..=max.0).map(move |x| (x, y)))
+}
+
+/// Positions with x + y in the u range and x - y in the v range,
+/// which is a box in coordinates rotated by 45 degrees, where the
+/// Chebyshev distance is half the Manhattan one
+fn rotated_box_iter(umin: i64, umax: i64, vmin: i64, vmax: i64) -> impl Iterator<Item = Pos> {
+    (umin..=umax).flat_map(move |u| {
+        (vmin..=vmax)
+            .filter(move |v| (u - v).rem_euclid(2) == 0)
+            .map(move |v| ((u + v) / 2, (u - v) / 2))
+    })
+}
+
+fn rotated_bounds(centers: &[Pos]) -> Option<(i64, i64, i64, i64)> {
+    let umin = centers.iter().map(|p| p.0 + p.1).min()?;
+    let umax = centers.iter().map(|p| p.0 + p.1).max()?;
+    let vmin = centers.iter().map(|p| p.0 - p.1).min()?;
+    let vmax = centers.iter().map(|p| p.0 - p.1).max()?;
+    Some((umin, umax, vmin, vmax))
+}
+
+fn cross(o: Pos, a: Pos, b: Pos) -> i128 {
+    (a.0 - o.0) as i128 * (b.1 - o.1) as i128 - (a.1 - o.1) as i128 * (b.0 - o.0) as i128
+}
+
+/// Vertices of the convex hull, without collinear points
+fn convex_hull(centers: &[Pos]) -> Vec<Pos> {
+    let mut points = centers.to_vec();
+    points.sort();
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    let mut hull = Vec::<Pos>::new();
+    for pass in 0..2 {
+        let start = hull.len();
+        for &p in &points {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0
+            {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        hull.pop();
+        if pass == 0 {
+            points.reverse();
+        }
+    }
+    hull
+}
+
+/// Whether each center claims an infinite number of cells
+pub fn infinite(metric: Metric, centers: &[Pos]) -> Vec<bool> {
+    let mut infinite = vec![false; centers.len()];
+    let Some((min, max)) = bounding_box(centers) else {
+        return infinite;
+    };
+    match metric {
+        Metric::Manhattan => {
+            // Stepping away from the box adds 1 to the distances to
+            // all centers, so whoever has a cell just outside of it has
+            // the whole ray beyond
+            for pos in box_iter((min.0 - 1, min.1 - 1), (max.0 + 1, max.1 + 1)) {
+                if pos.0 < min.0 || pos.0 > max.0 || pos.1 < min.1 || pos.1 > max.1 {
+                    if let Some(i) = nearest(metric, centers, pos) {
+                        infinite[i] = true;
+                    }
+                }
+            }
+        }
+        Metric::Chebyshev => {
+            // Same as above in rotated coordinates, where steps change
+            // u or v by 2
+            let (umin, umax, vmin, vmax) = rotated_bounds(centers).unwrap_or_default();
+            for pos in rotated_box_iter(umin - 2, umax + 2, vmin - 2, vmax + 2) {
+                let (u, v) = (pos.0 + pos.1, pos.0 - pos.1);
+                if u < umin || u > umax || v < vmin || v > vmax {
+                    if let Some(i) = nearest(metric, centers, pos) {
+                        infinite[i] = true;
+                    }
+                }
+            }
+        }
+        Metric::SquaredEuclidean => {
+            // The regions are the Voronoi cells, unbounded for the
+            // centers on the boundary of the convex hull; a center
+            // repeated has no cells at all
+            let hull = convex_hull(centers);
+            for (i, &c) in centers.iter().enumerate() {
+                if centers.iter().filter(|&&d| d == c).count() > 1 {
+                    continue;
+                }
+                infinite[i] = match hull.len() {
+                    0..=2 => true,
+                    n => (0..n).any(|j| {
+                        let (a, b) = (hull[j], hull[(j + 1) % n]);
+                        cross(a, b, c) == 0
+                            && a.0.min(b.0) <= c.0
+                            && c.0 <= a.0.max(b.0)
+                            && a.1.min(b.1) <= c.1
+                            && c.1 <= a.1.max(b.1)
+                    }),
+                };
+            }
+        }
+    }
+    infinite
+}
+
+/// Numerator and positive denominator
+type Fraction = (i128, i128);
+
+/// Cells of a bounded Voronoi cell, counted row by row: in each row,
+/// the cell is the interval where x satisfies a linear inequality for
+/// each other center
+fn voronoi_area(centers: &[Pos], i: usize) -> Option<usize> {
+    let c = centers[i];
+    let norm = |p: Pos| p.0 as i128 * p.0 as i128 + p.1 as i128 * p.1 as i128;
+    // Exclusive bounds of x in the row y, Some(None) if the row is
+    // empty and None if it is unbounded
+    let row = |y: i64| -> Option<Option<(Fraction, Fraction)>> {
+        let mut lo: Option<Fraction> = None;
+        let mut hi: Option<Fraction> = None;
+        for &d in centers {
+            if d == c {
+                continue;
+            }
+            // |p - c|^2 < |p - d|^2 <=> a * x < k
+            let a = 2 * (d.0 - c.0) as i128;
+            let k = norm(d) - norm(c) - 2 * y as i128 * (d.1 - c.1) as i128;
+            match a.signum() {
+                0 if k <= 0 => return Some(None),
+                0 => {}
+                1 => {
+                    if hi.is_none_or(|h| k * h.1 < h.0 * a) {
+                        hi = Some((k, a));
+                    }
+                }
+                _ => {
+                    if lo.is_none_or(|l| -k * l.1 > l.0 * -a) {
+                        lo = Some((-k, -a));
+                    }
+                }
+            }
+        }
+        let (lo, hi) = (lo?, hi?);
+        Some((lo.0 * hi.1 < hi.0 * lo.1).then_some((lo, hi)))
+    };
+    let count = |(lo, hi): (Fraction, Fraction)| {
+        let first = (lo.0 + lo.1).div_euclid(lo.1);
+        let last = (hi.0 - 1).div_euclid(hi.1);
+        (last - first + 1).max(0) as usize
+    };
+    let mut area = 0;
+    for dir in [1, -1] {
+        let mut y = if dir == 1 { c.1 } else { c.1 - 1 };
+        while let Some(bounds) = row(y)? {
+            area += count(bounds);
+            y += dir;
+        }
+    }
+    Some(area)
+}
+
+/// Cells of the largest box that is labeled cell by cell, each one
+/// against all centers
+pub const CELLS_MAX: u128 = 1_000_000;
+
+fn check_cells(cells: u128) -> Result<()> {
+    if cells > CELLS_MAX {
+        return Err(anyhow!(
+            "{} cells to label, more than the limit of {}",
+            cells,
+            CELLS_MAX
+        ));
+    }
+    Ok(())
+}
+
+/// Number of cells of the region of each center, None if infinite;
+/// an error if the regions are labeled cell by cell in a box with
+/// more than CELLS_MAX cells
+pub fn areas(metric: Metric, centers: &[Pos]) -> Result<Vec<Option<usize>>> {
+    let Some((min, max)) = bounding_box(centers) else {
+        return Ok(vec![]);
+    };
+    // The finite regions are within the box where the infinite ones
+    // are looked for
+    let cells: Box<dyn Iterator<Item = Pos>> = match metric {
+        Metric::Manhattan => {
+            check_cells(Viewport { min, max }.cells())?;
+            Box::new(box_iter(min, max))
+        }
+        Metric::Chebyshev => {
+            let (umin, umax, vmin, vmax) = rotated_bounds(centers).unwrap_or_default();
+            check_cells(((umax - umin + 1) as u128 * (vmax - vmin + 1) as u128).div_ceil(2))?;
+            Box::new(rotated_box_iter(umin, umax, vmin, vmax))
+        }
+        Metric::SquaredEuclidean => Box::new(iter::empty()),
+    };
+    let mut areas = infinite(metric, centers)
+        .iter()
+        .map(|&inf| if inf { None } else { Some(0) })
+        .collect::<Vec<_>>();
+    if metric == Metric::SquaredEuclidean {
+        for (i, area) in areas.iter_mut().enumerate() {
+            if area.is_some() && centers.iter().filter(|&&d| d == centers[i]).count() == 1 {
+                *area = voronoi_area(centers, i);
+            }
+        }
+    }
+    for pos in cells {
+        if let Some(area) = nearest(metric, centers, pos).and_then(|i| areas[i].as_mut()) {
+            *area += 1;
+        }
+    }
+    Ok(areas)
+}
+
+/// Between 1 and len centers in a side x side box
+#[cfg(test)]
+fn random_centers(rng: &mut testrand::Lcg, len: u64, side: u64) -> Vec<Pos> {
+    (0..1 + rng.below(len))
+        .map(|_| (rng.below(side) as i64, rng.below(side) as i64))
+        .collect()
+}
+
+#[cfg(test)]
+fn areas_brute(metric: Metric, centers: &[Pos], margin: i64) -> Vec<Option<usize>> {
+    let (min, max) = bounding_box(centers).unwrap();
+    let (min, max) = (
+        (min.0 - margin, min.1 - margin),
+        (max.0 + margin, max.1 + margin),
+    );
+    let mut areas = vec![Some(0); centers.len()];
+    for pos in box_iter(min, max) {
+        if let Some(i) = nearest(metric, centers, pos) {
+            if pos.0 == min.0 || pos.0 == max.0 || pos.1 == min.1 || pos.1 == max.1 {
+                areas[i] = None;
+            } else if let Some(area) = areas[i].as_mut() {
+                *area += 1;
+            }
+        }
+    }
+    areas
+}
+
+#[test]
+fn test_areas() -> Result<()> {
+    let centers = parser::parse(EXAMPLE.as_bytes())?;
+    assert_eq!(
+        areas(Metric::Manhattan, &centers)?,
+        vec![None, None, None, Some(9), Some(17), None]
+    );
+    for metric in Metric::ALL {
+        assert_eq!(areas(metric, &centers)?, areas_brute(metric, &centers, 40));
+    }
+    assert_eq!(nearest(Metric::Manhattan, &centers, (1, 4)), None);
+    // Repeated centers tie everywhere
+    let twins = vec![(0, 0), (0, 0), (2, 0), (0, 2), (-2, 0), (0, -2)];
+    for metric in Metric::ALL {
+        assert_eq!(areas(metric, &twins)?[..2], [Some(0), Some(0)]);
+    }
+    // The middle center of a side of the convex hull has an infinite
+    // strip for itself
+    let side = vec![(0, 0), (2, 0), (4, 0), (2, 3)];
+    assert_eq!(infinite(Metric::SquaredEuclidean, &side), vec![true; 4]);
+    // Differential test against labeling a large box, with
+    // pseudo-random centers
+    let metrics = [Metric::Manhattan, Metric::Chebyshev];
+    testrand::differential(
+        300,
+        |rng| random_centers(rng, 7, 9),
+        |centers| metrics.map(|metric| areas(metric, centers).ok()),
+        |centers| metrics.map(|metric| Some(areas_brute(metric, centers, 30))),
+    );
+    // The vertices of the bounded Voronoi cells are circumcenters of
+    // centers, with radius abc/4K; in a 5x5 box the sides are at most
+    // sqrt(32) and the area of a triangle at least 1/2, so the radius
+    // is below 91 and the cells are within a margin of 92
+    testrand::differential(
+        200,
+        |rng| random_centers(rng, 7, 5),
+        |centers| areas(Metric::SquaredEuclidean, centers).ok(),
+        |centers| Some(areas_brute(Metric::SquaredEuclidean, centers, 92)),
+    );
+    // Far apart centers are not labeled cell by cell
+    let far = vec![(0, 0), (20000, 20000), (10000, 10001)];
+    assert_eq!(
+        areas(Metric::Manhattan, &far).unwrap_err().to_string(),
+        "400040001 cells to label, more than the limit of 1000000"
+    );
+    assert_eq!(
+        areas(Metric::Chebyshev, &[(0, 0), (2000, 0)])
+            .unwrap_err()
+            .to_string(),
+        "2002001 cells to label, more than the limit of 1000000"
+    );
+    assert_eq!(
+        areas(Metric::SquaredEuclidean, &far)?,
+        vec![None, None, None]
+    );
+    Ok(())
+}
+
+pub fn largest_finite_area(metric: Metric, centers: &[Pos]) -> Result<Option<usize>> {
+    Ok(areas(metric, centers)?.into_iter().flatten().max())
+}
+
+/// Rectangle of cells, with both corners included
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Viewport {
+    pub min: Pos,
+    pub max: Pos,
+}
+
+impl Viewport {
+    /// Smallest viewport with all centers
+    pub fn around(centers: &[Pos]) -> Option<Viewport> {
+        let (min, max) = bounding_box(centers)?;
+        Some(Viewport { min, max })
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        (self.min.0..=self.max.0).contains(&pos.0) && (self.min.1..=self.max.1).contains(&pos.1)
+    }
+
+    pub fn cells(&self) -> u128 {
+        let side = |min: i64, max: i64| (max as i128 - min as i128 + 1) as u128;
+        side(self.min.0, self.max.0) * side(self.min.1, self.max.1)
+    }
+}
+
+impl fmt::Display for Viewport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{}:{},{}",
+            self.min.0, self.min.1, self.max.0, self.max.1
+        )
+    }
+}
+
+impl FromStr for Viewport {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Viewport> {
+        let invalid = || anyhow!("invalid viewport {:?}, expected xmin,ymin:xmax,ymax", s);
+        let (min, max) = s.split_once(':').ok_or_else(invalid)?;
+        let pos = |p: &str| -> Result<Pos> {
+            let (x, y) = p.split_once(',').ok_or_else(invalid)?;
+            Ok((
+                x.trim().parse().map_err(|_| invalid())?,
+                y.trim().parse().map_err(|_| invalid())?,
+            ))
+        };
+        let (min, max) = (pos(min)?, pos(max)?);
+        if min.0 > max.0 || min.1 > max.1 {
+            return Err(anyhow!("empty viewport {:?}", s));
+        }
+        Ok(Viewport { min, max })
+    }
+}
+
+/// Number of distinct labels of the centers in maps
+pub const LABELS: usize = 26;
+
+/// Letter of the center in maps, a to z and then around again, in
+/// uppercase at the center itself
+pub fn label(icenter: usize, center: bool) -> char {
+    let c = char::from(b'a' + (icenter % LABELS) as u8);
+    if center {
+        c.to_ascii_uppercase()
+    } else {
+        c
+    }
+}
+
+/// Regions of all centers, for checking inputs beyond the answer
+#[derive(Debug, Clone)]
+pub struct Regions {
+    pub metric: Metric,
+    pub centers: Vec<Pos>,
+    /// Area of the region of each center, None if infinite
+    pub areas: Vec<Option<usize>>,
+    /// Cells of the bounding box of the centers that are claimed by
+    /// no one, as they are at the same distance of more than one
+    pub ties: Vec<Pos>,
+}
+
+impl Regions {
+    /// Error if the bounding box of the centers has more than
+    /// CELLS_MAX cells
+    pub fn new(metric: Metric, centers: Vec<Pos>) -> Result<Regions> {
+        let ties = match Viewport::around(&centers) {
+            Some(v) => {
+                check_cells(v.cells())?;
+                box_iter(v.min, v.max)
+                    .filter(|&pos| nearest(metric, &centers, pos).is_none())
+                    .collect()
+            }
+            None => vec![],
+        };
+        let areas = areas(metric, &centers)?;
+        Ok(Regions {
+            metric,
+            centers,
+            areas,
+            ties,
+        })
+    }
+
+    /// Map of the cells in the viewport, the bounding box of the
+    /// centers by default, in the style of the puzzle: each cell with
+    /// the letter of the nearest center, uppercase at the center, and
+    /// '.' on ties; an error if the viewport has more than CELLS_MAX
+    /// cells
+    pub fn map(&self, viewport: Option<Viewport>) -> Result<String> {
+        let Some(viewport) = viewport.or_else(|| Viewport::around(&self.centers)) else {
+            return Ok(String::new());
+        };
+        check_cells(viewport.cells())?;
+        let mut map = String::new();
+        for y in viewport.min.1..=viewport.max.1 {
+            for x in viewport.min.0..=viewport.max.0 {
+                let pos = (x, y);
+                map.push(match self.centers.iter().position(|&c| c == pos) {
+                    Some(i) => label(i, true),
+                    None => nearest(self.metric, &self.centers, pos)
+                        .map(|i| label(i, false))
+                        .unwrap_or('.'),
+                });
+            }
+            map.push('\n');
+        }
+        Ok(map)
+    }
+}
+
+impl fmt::Display for Regions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.centers.len() > LABELS {
+            writeln!(
+                f,
+                "warning: {} centers but {} labels, the ones from center {} on repeat",
+                self.centers.len(),
+                LABELS,
+                LABELS + 1
+            )?;
+        }
+        for (i, (c, area)) in self.centers.iter().zip(&self.areas).enumerate() {
+            write!(f, "{} ({}, {}): ", label(i, true), c.0, c.1)?;
+            match area {
+                Some(area) => writeln!(f, "{}", area)?,
+                None => writeln!(f, "infinite")?,
+            }
+        }
+        writeln!(f, "ties in the bounding box: {}", self.ties.len())
+    }
+}
+
+#[test]
+fn test_regions() -> Result<()> {
+    let regions = Regions::new(Metric::Manhattan, parser::parse(EXAMPLE.as_bytes())?)?;
+    assert_eq!(
+        regions.to_string(),
+        "A (1, 1): infinite
+B (1, 6): infinite
+C (8, 3): infinite
+D (3, 4): 9
+E (5, 5): 17
+F (8, 9): infinite
+ties in the bounding box: 8
+"
+    );
+    let viewport = "0,0:9,9".parse::<Viewport>()?;
+    assert_eq!(viewport.to_string(), "0,0:9,9");
+    assert_eq!(
+        regions.map(Some(viewport))?,
+        "aaaaa.cccc
+aAaaa.cccc
+aaaddecccc
+aadddeccCc
+..dDdeeccc
+bb.deEeecc
+bBb.eeee..
+bbb.eeefff
+bbb.eeffff
+bbb.ffffFf
+"
+    );
+    assert_eq!(regions.map(None)?.lines().count(), 9);
+    assert_eq!(regions.map(Some("3,4:5,5".parse()?))?, "Dde\ndeE\n");
+    assert_eq!(
+        regions
+            .map(Some(
+                "-9223372036854775808,0:9223372036854775807,0".parse()?
+            ))
+            .unwrap_err()
+            .to_string(),
+        "18446744073709551616 cells to label, more than the limit of 1000000"
+    );
+    assert!("1,1".parse::<Viewport>().is_err());
+    assert!("5,5:1,1".parse::<Viewport>().is_err());
+    assert_eq!(Regions::new(Metric::Manhattan, vec![])?.map(None)?, "");
+    assert!(Regions::new(Metric::SquaredEuclidean, vec![(0, 0), (20000, 20000)]).is_err());
+    // More centers than labels, in a row, 3 cells apart
+    let row = (0..30).map(|i| (3 * i, 0)).collect::<Vec<_>>();
+    let regions = Regions::new(Metric::Manhattan, row)?;
+    let text = regions.to_string();
+    assert!(text.starts_with(
+        "warning: 30 centers but 26 labels, the ones from center 27 on repeat\nA (0, 0): "
+    ));
+    assert!(text.contains("\nA (78, 0): infinite\n"));
+    let map = regions.map(None)?;
+    assert_eq!(&map[..6], "AabBbc");
+    assert_eq!(&map[72..], "YyzZzaAabBbcCcdD\n");
+    Ok(())
+}
+
+/// First x in lo..hi for which pred is true, hi if none, for preds
+/// that are false and then true
+fn partition_point(mut lo: i64, mut hi: i64, pred: impl Fn(i64) -> bool) -> i64 {
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo
+}
+
+/// Sum of floor((a * i + b) / m) for i in 0..n, in O(log m)
+fn floor_sum(mut n: u128, mut m: u128, mut a: u128, mut b: u128) -> u128 {
+    let mut sum = 0;
+    loop {
+        if a >= m {
+            sum += n * (n - 1) / 2 * (a / m);
+            a %= m;
+        }
+        if b >= m {
+            sum += n * (b / m);
+            b %= m;
+        }
+        let ymax = a * n + b;
+        if ymax < m {
+            return sum;
+        }
+        (n, b) = (ymax / m, ymax % m);
+        (m, a) = (a, m);
+    }
+}
+
+/// A run of consecutive positions where the sum of the distances to
+/// the coordinates goes up linearly, as (sum at the first position,
+/// slope, number of positions)
+type Run = (i128, i128, i128);
+
+/// Runs that cover each position where the sum of the distances to
+/// the sorted coordinates is below maxdist exactly once: the
+/// coordinates themselves, the gaps between them, walked towards the
+/// smaller sum, and the tails, walked away from the coordinates. The
+/// slope of a gap is the number of coordinates behind it minus the
+/// number of the ones ahead.
+fn runs(coords: &[i64], maxdist: i128) -> Vec<Run> {
+    let (Some(&min), Some(&max)) = (coords.first(), coords.last()) else {
+        return vec![];
+    };
+    let n = coords.len() as i128;
+    let total = |x: i128| -> i128 { coords.iter().map(|&c| (i128::from(c) - x).abs()).sum() };
+    let mut runs = vec![
+        (total(i128::from(min) - 1), n, i128::MAX),
+        (total(i128::from(max) + 1), n, i128::MAX),
+    ];
+    let mut points = coords.iter().map(|&c| i128::from(c)).collect::<Vec<_>>();
+    points.dedup();
+    for (i, &p) in points.iter().enumerate() {
+        runs.push((total(p), 0, 1));
+        let Some(&q) = points.get(i + 1) else {
+            continue;
+        };
+        if q - p > 1 {
+            let behind = coords.partition_point(|&c| i128::from(c) <= p) as i128;
+            let slope = 2 * behind - n;
+            if slope >= 0 {
+                runs.push((total(p + 1), slope, q - p - 1));
+            } else {
+                runs.push((total(q - 1), -slope, q - p - 1));
+            }
+        }
+    }
+    runs.into_iter()
+        .filter_map(|(first, slope, len)| {
+            let below = match slope {
+                _ if first >= maxdist => 0,
+                0 => len,
+                _ => len.min((maxdist - first + slope - 1) / slope),
+            };
+            (below > 0).then_some((first, slope, below))
+        })
+        .collect()
+}
+
+/// Number of pairs of positions, one of each run, whose sums add up
+/// to less than maxdist: for each position of the first run, a prefix
+/// of the second one, whole for the first positions and then floor
+/// divisions that add up with floor_sum
+fn run_pairs(x: Run, y: Run, maxdist: i128) -> u128 {
+    // s * t + r * u <= rest, for t in 0..m and u in 0..k
+    let Ok(rest) = u128::try_from(maxdist - x.0 - y.0 - 1) else {
+        return 0;
+    };
+    let (s, m) = (x.1 as u128, x.2 as u128);
+    let (r, k) = (y.1 as u128, y.2 as u128);
+    let m = rest.checked_div(s).map_or(m, |t| m.min(t + 1));
+    if r == 0 {
+        return m * k;
+    }
+    // Positions of x for which all of y is below
+    let whole = match rest.checked_sub(r * (k - 1)) {
+        None => 0,
+        Some(d) => d.checked_div(s).map_or(m, |t| m.min(t + 1)),
+    };
+    let partial = m - whole;
+    if partial == 0 {
+        return whole * k;
+    }
+    whole * k + partial + floor_sum(partial, r, s, rest - s * (m - 1))
+}
+
+/// Number of cells whose Manhattan distances to all centers add up to
+/// less than maxdist. The sum is the sum of the distances in x plus the
+/// one in y, each of them piecewise linear, so we count the pairs of
+/// positions in each pair of linear runs.
+pub fn safe_area_manhattan(centers: &[Pos], maxdist: i64) -> u128 {
+    let mut xs = centers.iter().map(|c| c.0).collect::<Vec<_>>();
+    let mut ys = centers.iter().map(|c| c.1).collect::<Vec<_>>();
+    xs.sort_unstable();
+    ys.sort_unstable();
+    let maxdist = i128::from(maxdist);
+    let yruns = runs(&ys, maxdist);
+    runs(&xs, maxdist)
+        .into_iter()
+        .flat_map(|x| yruns.iter().map(move |&y| run_pairs(x, y, maxdist)))
+        .sum()
+}
+
+/// Number of cells whose distances to all centers add up to less than
+/// maxdist
+pub fn safe_area(metric: Metric, centers: &[Pos], maxdist: i64) -> u128 {
+    match metric {
+        Metric::Manhattan => safe_area_manhattan(centers, maxdist),
+        _ => safe_area_rows(metric, centers, maxdist) as u128,
+    }
+}
+
+/// The sum is convex, so each row has an interval of cells below
+/// maxdist around its minimum, and the rows with any form an interval
+/// around the one with the smallest minimum.
+fn safe_area_rows(metric: Metric, centers: &[Pos], maxdist: i64) -> usize {
+    let Some((min, max)) = bounding_box(centers) else {
+        return 0;
+    };
+    // All distances are at least as large as the difference in each
+    // coordinate, or its square; beyond 2^61 the area wouldn't fit in
+    // an usize anyway
+    let reach = match metric {
+        Metric::SquaredEuclidean => maxdist.max(0).isqrt() + 1,
+        _ => maxdist,
+    }
+    .clamp(0, 1 << 61);
+    let (xlo, xhi) = (min.0.saturating_sub(reach), max.0.saturating_add(reach));
+    let (ylo, yhi) = (min.1.saturating_sub(reach), max.1.saturating_add(reach));
+    let total = |pos: Pos| -> i128 { centers.iter().map(|&c| metric.distance(c, pos)).sum() };
+    let maxdist = i128::from(maxdist);
+    let row_min = |y: i64| {
+        let x = partition_point(xlo, xhi, |x| total((x + 1, y)) >= total((x, y)));
+        (x, total((x, y)))
+    };
+    let row_area = |y: i64| -> usize {
+        let (x, t) = row_min(y);
+        if t >= maxdist {
+            return 0;
+        }
+        let first = partition_point(xlo, x, |x| total((x, y)) < maxdist);
+        let last = partition_point(x, xhi.saturating_add(1), |x| total((x, y)) >= maxdist);
+        (last - first) as usize
+    };
+    let ybest = partition_point(ylo, yhi, |y| row_min(y + 1).1 >= row_min(y).1);
+    let mut area = 0;
+    for dir in [1, -1] {
+        let mut y = if dir == 1 { ybest } else { ybest - 1 };
+        loop {
+            let a = row_area(y);
+            if a == 0 {
+                break;
+            }
+            area += a;
+            y += dir;
+        }
+    }
+    area
+}
+
+#[cfg(test)]
+fn safe_area_brute(metric: Metric, centers: &[Pos], maxdist: i64) -> u128 {
+    let (min, max) = bounding_box(centers).unwrap();
+    box_iter(
+        (min.0 - maxdist, min.1 - maxdist),
+        (max.0 + maxdist, max.1 + maxdist),
+    )
+    .filter(|&pos| {
+        centers
+            .iter()
+            .map(|&c| metric.distance(c, pos))
+            .sum::<i128>()
+            < i128::from(maxdist)
+    })
+    .count() as u128
+}
+
+#[test]
+fn test_safe_area() -> Result<()> {
+    testrand::differential(
+        200,
+        |rng| (random_centers(rng, 5, 9), rng.below(60) as i64),
+        |(centers, maxdist)| Metric::ALL.map(|metric| safe_area(metric, centers, *maxdist)),
+        |(centers, maxdist)| Metric::ALL.map(|metric| safe_area_brute(metric, centers, *maxdist)),
+    );
+    // Larger distances, against the rows
+    testrand::differential(
+        200,
+        |rng| (random_centers(rng, 5, 9), rng.below(800) as i64),
+        |(centers, maxdist)| safe_area_manhattan(centers, *maxdist),
+        |(centers, maxdist)| safe_area_rows(Metric::Manhattan, centers, *maxdist) as u128,
+    );
+    // The region is about a diamond of radius maxdist / n
+    let centers = parser::parse(EXAMPLE.as_bytes())?;
+    let area = safe_area_manhattan(&centers, 6_000_000);
+    assert!(
+        area.abs_diff(2 * 1_000_000 * 1_000_000) < 10_000_000,
+        "{}",
+        area
+    );
+    // Far apart centers, up to the limits of the coordinates, only cost
+    // the width of the safe strip
+    assert_eq!(safe_area_manhattan(&[(0, 0), (2_000_000_000, 0)], 10000), 0);
+    assert_eq!(
+        safe_area_manhattan(&[(i64::MIN, 0), (i64::MAX, 0)], i64::MAX),
+        0
+    );
+    assert_eq!(safe_area_manhattan(&[(i64::MAX, i64::MIN)], 3), 13);
+    let far = [(1 << 40, -(1 << 40)), ((1 << 40) + 10, 5 - (1 << 40))];
+    assert_eq!(
+        safe_area_manhattan(&far, 40),
+        safe_area_rows(Metric::Manhattan, &[(0, 0), (10, 5)], 40) as u128
+    );
+    // A single center has a diamond of radius maxdist - 1, of
+    // 2r^2 + 2r + 1 cells, which doesn't fit in an i128 for the
+    // largest maxdist
+    let r = i64::MAX as u128 - 1;
+    assert_eq!(
+        safe_area_manhattan(&[(0, 0)], i64::MAX),
+        2 * r * r + 2 * r + 1
+    );
+    Ok(())
+}
+
+pub fn process_a(bufin: impl BufRead) -> Result<usize> {
+    let centers = parser::parse(bufin)?;
+    largest_finite_area(Metric::Manhattan, &centers)?.ok_or_else(|| anyhow!("solution not found"))
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    assert_eq!(process_a(EXAMPLE.as_bytes())?, 17);
+    // Translated far from the origin, to negative x
+    let moved = parser::parse(EXAMPLE.as_bytes())?
+        .into_iter()
+        .map(|(x, y)| format!("{}, {}\n", x - 5000, y + 1_000_000))
+        .collect::<String>();
+    assert_eq!(process_a(moved.as_bytes())?, 17);
+    assert_eq!(process_b(32, moved.as_bytes())?, 16);
+    Ok(())
+}
+
+pub fn process_b(maxdist: usize, bufin: impl BufRead) -> Result<u128> {
+    let centers = parser::parse(bufin)?;
+    let maxdist = i64::try_from(maxdist).map_err(|_| {
+        anyhow!(
+            "distance {} out of range, the limit is {}",
+            maxdist,
+            i64::MAX
+        )
+    })?;
+    Ok(safe_area(Metric::Manhattan, &centers, maxdist))
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    assert_eq!(process_b(32, EXAMPLE.as_bytes())?, 16);
+    // The region is about a diamond of radius maxdist / n
+    let area = process_b(100_000_000_000, EXAMPLE.as_bytes())?;
+    let r = 100_000_000_000 / 6;
+    assert!(area.abs_diff(2 * r * r) < 10 * r, "{}", area);
+    assert!(process_b(usize::MAX, EXAMPLE.as_bytes()).is_err());
+    let centers = parser::parse(EXAMPLE.as_bytes())?;
+    assert_eq!(
+        safe_area(Metric::Chebyshev, &centers, 32),
+        safe_area_brute(Metric::Chebyshev, &centers, 32)
+    );
+    // Far apart centers don't overflow the sums of the distances
+    let far = [
+        (0, 0),
+        (3_000_000_000, 0),
+        (0, 3_000_000_000),
+        (3_000_000_000, 3_000_000_000),
+    ];
+    for metric in Metric::ALL {
+        assert_eq!(safe_area(metric, &far, 10000), 0);
+    }
+    let square = far.map(|(x, y)| (x / 1_000_000_000, y / 1_000_000_000));
+    assert_eq!(
+        safe_area(Metric::SquaredEuclidean, &square, 40),
+        safe_area_brute(Metric::SquaredEuclidean, &square, 40)
     );
     Ok(())
 }
@@ -2,10 +2,14 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-#[cfg(test)]
+use andex::*;
+use anyhow::anyhow;
 use anyhow::Result;
+use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Write;
+use std::io::BufRead;
+use std::iter;
 
 pub const EXAMPLE: &str = "Step C must be finished before step A can begin.
 Step C must be finished before step F can begin.
@@ -120,3 +124,93 @@ fn test_render() -> Result<()> {
     );
     Ok(())
 }
+
+pub fn process_a(bufin: impl BufRead) -> Result<String> {
+    let deps = parser::parse(bufin)?;
+    let steps = deps
+        .iter()
+        .flat_map(|&(s1, s2)| iter::once(s1).chain(iter::once(s2)))
+        .collect::<HashSet<_>>();
+    let mut done: Vec<Step> = vec![];
+    while done.len() < steps.len() {
+        let mut ready = steps
+            .iter()
+            .filter(|s| s.got_ready(&deps, &done))
+            .collect::<Vec<_>>();
+        ready.sort();
+        let step = ready.first().ok_or_else(|| anyhow!("dependency cycle"))?;
+        done.push(**step);
+    }
+    Ok(done.into_iter().map(char::from).collect::<String>())
+}
+
+#[cfg(test)]
+const CYCLE: &str = "Step A must be finished before step B can begin.
+Step B must be finished before step A can begin.
+";
+
+#[test]
+fn test_a() -> Result<()> {
+    assert_eq!(process_a(EXAMPLE.as_bytes())?, "CABDFE");
+    assert!(process_a(CYCLE.as_bytes()).is_err());
+    Ok(())
+}
+
+enum IworkerMarker {}
+type Iworker = Andex<IworkerMarker, 5>;
+type WorkerTime = andex::andex_array!(Iworker, usize);
+type WorkerStep = andex::andex_array!(Iworker, Option<Step>);
+
+pub fn process_b(bufin: impl BufRead) -> Result<usize> {
+    let deps = parser::parse(bufin)?;
+    let steps = deps
+        .iter()
+        .flat_map(|&(s1, s2)| iter::once(s1).chain(iter::once(s2)))
+        .collect::<HashSet<_>>();
+    let mut done: Vec<Step> = vec![];
+    let mut started = HashSet::<Step>::new();
+    let mut now = 0;
+    let mut wtime = WorkerTime::default();
+    let mut wstep = WorkerStep::default();
+    while done.len() < steps.len() {
+        // Check who finished:
+        for iw in Iworker::iter() {
+            if wtime[iw] <= now {
+                if let Some(step) = wstep[iw].take() {
+                    done.push(step);
+                    wtime[iw] = 0;
+                }
+            }
+        }
+        // New ready steps:
+        let mut ready = steps
+            .iter()
+            .filter(|s| s.got_ready(&deps, &done) && !started.contains(s))
+            .collect::<Vec<_>>();
+        // Assign workers:
+        for iw in Iworker::iter() {
+            if wtime[iw] == 0 {
+                if let Some(step) = ready.pop() {
+                    wtime[iw] = now + step.cost() as usize;
+                    wstep[iw] = Some(*step);
+                    started.insert(*step);
+                }
+            }
+        }
+        // Next relevant time:
+        if let Some(now_next) = wtime.iter().filter(|&t| *t > 0).min() {
+            now = *now_next;
+        } else if done.len() < steps.len() {
+            return Err(anyhow!("dependency cycle"));
+        }
+    }
+    Ok(now)
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    // assert_eq!(process_b(EXAMPLE.as_bytes())?, 15);
+    assert_eq!(process_b(EXAMPLE.as_bytes())?, 253);
+    assert!(process_b(CYCLE.as_bytes()).is_err());
+    Ok(())
+}
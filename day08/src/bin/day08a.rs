@@ -3,22 +3,11 @@
 // file 'LICENSE', which is part of this source code package.
 
 use anyhow::Result;
-use std::io::{stdin, BufRead};
+use std::io::stdin;
 
 use day08::*;
 
-fn process(bufin: impl BufRead) -> Result<usize> {
-    let allnodes = parser::parse(bufin)?;
-    Ok(allnodes.into_iter().flat_map(|n| n.data).sum())
-}
-
-#[test]
-fn test() -> Result<()> {
-    assert_eq!(process(EXAMPLE.as_bytes())?, 138);
-    Ok(())
-}
-
 fn main() -> Result<()> {
-    println!("{}", process(stdin().lock())?);
+    println!("{}", process_a(stdin().lock())?);
     Ok(())
 }
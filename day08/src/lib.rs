@@ -2,8 +2,8 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-#[cfg(test)]
 use anyhow::Result;
+use std::io::BufRead;
 
 use andex::*;
 
@@ -29,6 +29,7 @@ pub mod parser {
     use anyhow::Result;
     use nom::character::complete as character;
     use nom::combinator;
+    use nom::error;
     use nom::IResult;
     use std::io::BufRead;
 
@@ -47,8 +48,10 @@ pub mod parser {
     }
 
     pub fn node<'a>(allnodes: &mut Vec<Node>, input: &'a str) -> IResult<&'a str, Inode> {
+        let start = input;
         let (mut input, (nchildren, ndata)) = header(input)?;
-        let inode = Inode::try_from(allnodes.len()).unwrap();
+        let inode = Inode::try_from(allnodes.len())
+            .map_err(|_| nom::Err::Failure(error::Error::new(start, error::ErrorKind::TooLarge)))?;
         allnodes.push(Node {
             id: inode,
             ..Default::default()
@@ -85,9 +88,49 @@ pub mod parser {
     }
 }
 
+pub fn node_value(nodes: &[Node], inode: Inode) -> usize {
+    let node = &nodes[usize::from(inode)];
+    if node.children.is_empty() {
+        node.data.iter().sum()
+    } else {
+        node.data
+            .iter()
+            .filter_map(|&i| {
+                if 0 < i && i <= node.children.len() {
+                    Some(node_value(nodes, node.children[i - 1]))
+                } else {
+                    None
+                }
+            })
+            .sum()
+    }
+}
+
 #[test]
 fn test() -> Result<()> {
     let allnodes = parser::parse(EXAMPLE.as_bytes())?;
     assert_eq!(allnodes[0].id, Inode::FIRST);
     Ok(())
 }
+
+pub fn process_a(bufin: impl BufRead) -> Result<usize> {
+    let allnodes = parser::parse(bufin)?;
+    Ok(allnodes.into_iter().flat_map(|n| n.data).sum())
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    assert_eq!(process_a(EXAMPLE.as_bytes())?, 138);
+    Ok(())
+}
+
+pub fn process_b(bufin: impl BufRead) -> Result<usize> {
+    let allnodes = parser::parse(bufin)?;
+    Ok(node_value(&allnodes, Inode::FIRST))
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    assert_eq!(process_b(EXAMPLE.as_bytes())?, 66);
+    Ok(())
+}
@@ -3,23 +3,11 @@
 // file 'LICENSE', which is part of this source code package.
 
 use anyhow::Result;
-use std::io::{stdin, BufRead};
+use std::io::stdin;
 
 use day09::*;
 
-fn process(bufin: impl BufRead) -> Result<usize> {
-    let (players, lastmarble) = parser::parse(bufin)?;
-    let mut state = State::new(players, lastmarble);
-    Ok(state.resolve().max_score())
-}
-
-#[test]
-fn test() -> Result<()> {
-    assert_eq!(process(EXAMPLE.as_bytes())?, 32);
-    Ok(())
-}
-
 fn main() -> Result<()> {
-    println!("{}", process(stdin().lock())?);
+    println!("{}", process_a(stdin().lock())?);
     Ok(())
 }
@@ -2,19 +2,12 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-// use anyhow::anyhow;
 use anyhow::Result;
-use std::io::{stdin, BufRead};
+use std::io::stdin;
 
 use day09::*;
 
-fn process(bufin: impl BufRead) -> Result<usize> {
-    let (players, lastmarble) = parser::parse(bufin)?;
-    let mut state = State::new(players, 100 * lastmarble);
-    Ok(state.resolve().max_score())
-}
-
 fn main() -> Result<()> {
-    println!("{}", process(stdin().lock())?);
+    println!("{}", process_b(stdin().lock())?);
     Ok(())
 }
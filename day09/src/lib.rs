@@ -2,9 +2,9 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-#[cfg(test)]
 use anyhow::Result;
 use std::fmt;
+use std::io::BufRead;
 
 pub const EXAMPLE: &str = "9 players; last marble is worth 25 points\n";
 
@@ -39,7 +39,7 @@ impl State {
 
     pub fn play(&mut self) {
         self.turn += 1;
-        if self.turn % 23 == 0 {
+        if self.turn.is_multiple_of(23) {
             self.scores[self.nextplayer] += self.turn;
             let mcurr = (0..7).fold(self.mcurr, |mcurr, _| self.mlinks[mcurr].0);
             let prev = self.mlinks[mcurr].0;
@@ -111,7 +111,7 @@ pub mod parser {
     use super::Marble;
 
     pub fn line(input: &str) -> IResult<&str, (usize, Marble)> {
-        let (input, players) = character::u32(input)?;
+        let (input, players) = combinator::verify(character::u32, |&p| p > 0)(input)?;
         let (input, _) = bytes::tag(" players; last marble is worth ")(input)?;
         let (input, lastmarble) = character::u32(input)?;
         let (input, _) = bytes::tag(" points")(input)?;
@@ -132,6 +132,7 @@ pub mod parser {
 #[test]
 fn test() -> Result<()> {
     assert_eq!(parser::parse(EXAMPLE.as_bytes())?, (9, 25));
+    assert!(parser::parse("0 players; last marble is worth 25 points\n".as_bytes()).is_err());
     Ok(())
 }
 
@@ -147,3 +148,21 @@ fn test_resolve() -> Result<()> {
     assert_eq!(State::new(30, 580700).resolve().max_score(), 320997431);
     Ok(())
 }
+
+pub fn process_a(bufin: impl BufRead) -> Result<usize> {
+    let (players, lastmarble) = parser::parse(bufin)?;
+    let mut state = State::new(players, lastmarble);
+    Ok(state.resolve().max_score())
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    assert_eq!(process_a(EXAMPLE.as_bytes())?, 32);
+    Ok(())
+}
+
+pub fn process_b(bufin: impl BufRead) -> Result<usize> {
+    let (players, lastmarble) = parser::parse(bufin)?;
+    let mut state = State::new(players, 100 * lastmarble);
+    Ok(state.resolve().max_score())
+}
@@ -2,26 +2,16 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-// use anyhow::anyhow;
 use anyhow::Result;
-use std::io::{stdin, BufRead};
+use std::io::stdin;
 
 use day10::*;
 
-fn process(bufin: impl BufRead) -> Result<usize> {
-    let posvel = parser::parse(bufin)?;
-    let (seconds, word) = get_word(posvel);
-    println!("{}", word);
-    Ok(seconds)
-}
-
-#[test]
-fn test() -> Result<()> {
-    assert_eq!(process(EXAMPLE.as_bytes())?, 3);
-    Ok(())
-}
-
 fn main() -> Result<()> {
-    println!("{}", process(stdin().lock())?);
+    // The word and then the seconds it takes to show up
+    let posvel = parser::parse(stdin().lock())?;
+    let (seconds, word) = get_word(posvel)?;
+    println!("{}", word);
+    println!("{}", seconds);
     Ok(())
 }
@@ -2,10 +2,12 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
+use std::collections::HashSet;
 use std::fmt;
+use std::io::BufRead;
 use std::ops;
 
-#[cfg(test)]
+use anyhow::anyhow;
 use anyhow::Result;
 
 pub const EXAMPLE: &str = "position=< 9,  1> velocity=< 0,  2>
@@ -41,11 +43,11 @@ position=<14,  7> velocity=<-2,  0>
 position=<-3,  6> velocity=< 2, -1>
 ";
 
-#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
-pub struct Pos(i32, i32);
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Pos(pub i32, pub i32);
 
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
-pub struct Vel(i32, i32);
+pub struct Vel(pub i32, pub i32);
 
 impl ops::Add<Vel> for Pos {
     type Output = Pos;
@@ -54,16 +56,26 @@ impl ops::Add<Vel> for Pos {
     }
 }
 
+impl Pos {
+    /// The position after moving, None if it's out of range
+    pub fn checked_add(self, vel: Vel) -> Option<Pos> {
+        Some(Pos(self.0.checked_add(vel.0)?, self.1.checked_add(vel.1)?))
+    }
+}
+
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
 pub struct Posvel {
     pv: Vec<(Pos, Vel)>,
 }
 
 impl Posvel {
-    pub fn step(&mut self) {
+    pub fn step(&mut self) -> Result<()> {
         for (p, v) in self.pv.iter_mut() {
-            *p = *p + *v;
+            *p = p
+                .checked_add(*v)
+                .ok_or_else(|| anyhow!("point at {:?} moved out of range", p))?;
         }
+        Ok(())
     }
 
     pub fn valid(&self) -> bool {
@@ -87,16 +99,34 @@ impl Posvel {
         false
     }
 
+    pub fn positions(&self) -> impl Iterator<Item = &Pos> {
+        self.pv.iter().map(|(p, _)| p)
+    }
+
+    pub fn velocities(&self) -> impl Iterator<Item = &Vel> {
+        self.pv.iter().map(|(_, v)| v)
+    }
+
+    /// Returns the top-left and bottom-right corners of the points
+    pub fn bbox(&self) -> Option<(Pos, Pos)> {
+        let xmin = self.pv.iter().map(|(p, _)| p.0).min()?;
+        let xmax = self.pv.iter().map(|(p, _)| p.0).max()?;
+        let ymin = self.pv.iter().map(|(p, _)| p.1).min()?;
+        let ymax = self.pv.iter().map(|(p, _)| p.1).max()?;
+        Some((Pos(xmin, ymin), Pos(xmax, ymax)))
+    }
+
     pub fn contains(&self, p0: &Pos) -> bool {
         self.pv.iter().any(|(p, _)| p == p0)
     }
 
     pub fn verticality(&self) -> u32 {
+        let positions = self.positions().collect::<HashSet<_>>();
         self.pv
             .iter()
             .map(|(p, _)| {
-                let down = Pos(p.0, p.1 + 1);
-                if self.contains(&down) {
+                let down = p.1.checked_add(1).map(|y| Pos(p.0, y));
+                if down.is_some_and(|down| positions.contains(&&down)) {
                     1
                 } else {
                     0
@@ -114,10 +144,9 @@ impl From<Vec<(Pos, Vel)>> for Posvel {
 
 impl fmt::Display for Posvel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let xmin = self.pv.iter().map(|(p, _)| p.0).min().unwrap();
-        let xmax = self.pv.iter().map(|(p, _)| p.0).max().unwrap();
-        let ymin = self.pv.iter().map(|(p, _)| p.1).min().unwrap();
-        let ymax = self.pv.iter().map(|(p, _)| p.1).max().unwrap();
+        let Some((Pos(xmin, ymin), Pos(xmax, ymax))) = self.bbox() else {
+            return Ok(());
+        };
         for y in ymin..=ymax {
             for x in xmin..=xmax {
                 let pos = Pos(x, y);
@@ -134,18 +163,28 @@ impl fmt::Display for Posvel {
     }
 }
 
-pub fn get_word(mut posvel: Posvel) -> (usize, Posvel) {
+/// Seconds that the points are moved looking for the word, well above
+/// the ones of the puzzle inputs
+const SECONDS_MAX: usize = 100_000;
+
+pub fn get_word(mut posvel: Posvel) -> Result<(usize, Posvel)> {
     let mut i = 0;
     let mut best = (posvel.verticality(), i, posvel.clone());
     while posvel.valid() {
+        if i == SECONDS_MAX {
+            return Err(anyhow!(
+                "points did not converge in {} seconds",
+                SECONDS_MAX
+            ));
+        }
         i += 1;
-        posvel.step();
+        posvel.step()?;
         let v = posvel.verticality();
         if v > best.0 {
             best = (v, i, posvel.clone());
         }
     }
-    (best.1, best.2)
+    Ok((best.1, best.2))
 }
 
 pub mod parser {
@@ -235,3 +274,53 @@ fn test() -> Result<()> {
 
     Ok(())
 }
+
+pub fn process_a(bufin: impl BufRead) -> Result<Posvel> {
+    let posvel = parser::parse(bufin)?;
+    let (_seconds, word) = get_word(posvel)?;
+    Ok(word)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    let word = process_a(EXAMPLE.as_bytes())?.to_string();
+    assert_eq!(
+        word.lines().map(str::trim_end).collect::<Vec<_>>(),
+        vec![
+            "#   #  ###",
+            "#   #   #",
+            "#   #   #",
+            "#####   #",
+            "#   #   #",
+            "#   #   #",
+            "#   #   #",
+            "#   #  ###",
+        ]
+    );
+    Ok(())
+}
+
+pub fn process_b(bufin: impl BufRead) -> Result<usize> {
+    let posvel = parser::parse(bufin)?;
+    let (seconds, _word) = get_word(posvel)?;
+    Ok(seconds)
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    assert_eq!(process_b(EXAMPLE.as_bytes())?, 3);
+    let far = "position=<-2000000000, 0> velocity=< 1, 0>\n\
+               position=< 2000000000, 0> velocity=<-1, 0>\n";
+    assert_eq!(
+        process_b(far.as_bytes()).unwrap_err().to_string(),
+        "points did not converge in 100000 seconds"
+    );
+    let fast = "position=<-2, 0> velocity=< 1, 0>\n\
+                position=< 2, 0> velocity=<-1, 0>\n\
+                position=< 2147483000, 0> velocity=< 1000, 0>\n";
+    assert_eq!(
+        process_b(fast.as_bytes()).unwrap_err().to_string(),
+        "point at Pos(2147483000, 0) moved out of range"
+    );
+    Ok(())
+}
@@ -2,8 +2,9 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
-#[cfg(test)]
 use anyhow::Result;
+use std::collections::HashMap;
+use std::io::BufRead;
 
 use rayon::prelude::*;
 
@@ -36,9 +37,10 @@ pub mod parser {
 }
 
 pub fn cell_power_calc(xy: Xy, serial: i64) -> i64 {
-    let rackid = xy.0 as i64 + 10;
-    let p = (rackid * xy.1 as i64 + serial) * rackid;
-    ((p - p % 100) / 100) % 10 - 5
+    // i128 holds the product for any serial
+    let rackid = xy.0 as i128 + 10;
+    let p = (rackid * xy.1 as i128 + serial as i128) * rackid;
+    (((p - p % 100) / 100) % 10 - 5) as i64
 }
 
 pub fn grid_coords_iter() -> impl Iterator<Item = Xy> {
@@ -75,5 +77,111 @@ fn test_calc() -> Result<()> {
     assert_eq!(cell_power_calc((122, 79), 57), -5);
     assert_eq!(cell_power_calc((217, 196), 39), 0);
     assert_eq!(cell_power_calc((101, 153), 71), 4);
+    assert_eq!(cell_power_calc((300, 300), i64::MAX), -4);
+    assert_eq!(cell_power_calc((300, 300), i64::MIN), -9);
+    Ok(())
+}
+
+/// Summed-area table of the power of the grid, with a zero border,
+/// for the power of any square in constant time
+#[derive(Debug, Clone)]
+pub struct PowerSums(Vec<Vec<i64>>);
+
+impl PowerSums {
+    pub fn new(serial: i64) -> PowerSums {
+        let mut sums = vec![vec![0; 301]; 301];
+        for y in 1..=300 {
+            for x in 1..=300 {
+                sums[y][x] = cell_power_calc((x, y), serial) + sums[y - 1][x] + sums[y][x - 1]
+                    - sums[y - 1][x - 1];
+            }
+        }
+        PowerSums(sums)
+    }
+
+    /// Power of the square with the top-left cell at xy
+    pub fn square(&self, (x, y): Xy, size: usize) -> i64 {
+        let sums = &self.0;
+        let (x1, y1) = (x + size - 1, y + size - 1);
+        sums[y1][x1] - sums[y - 1][x1] - sums[y1][x - 1] + sums[y - 1][x - 1]
+    }
+}
+
+#[test]
+fn test_sums() -> Result<()> {
+    let sums = PowerSums::new(18);
+    assert_eq!(sums.square((33, 45), 3), 29);
+    assert_eq!(sums.square((90, 269), 16), 113);
+    assert_eq!(sums.square((300, 300), 1), cell_power_calc((300, 300), 18));
+    Ok(())
+}
+
+pub fn process_a(bufin: impl BufRead) -> Result<(usize, usize)> {
+    let serial = parser::parse(bufin)?;
+    let grid = grid_coords_par_iter(1)
+        .map(|xy| (xy, cell_power_calc(xy, serial)))
+        .collect::<HashMap<_, _>>();
+    let maxpower = grid_coords_par_iter(1)
+        .filter(|xy| xy.0 <= 298 && xy.1 <= 298)
+        .map(|xy| {
+            let power = square_coords_iter(xy).map(|xy| grid[&xy]).sum::<i64>();
+            (power, xy)
+        })
+        .max();
+    Ok(maxpower.unwrap().1)
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    assert_eq!(process_a("18\n".as_bytes())?, (33, 45));
+    assert_eq!(process_a("42\n".as_bytes())?, (21, 61));
+    Ok(())
+}
+
+pub fn process_b(bufin: impl BufRead) -> Result<(Xy, usize)> {
+    let serial = parser::parse(bufin)?;
+    let mut oldgrid2 = grid_coords_par_iter(0)
+        .map(|xy| ((xy, 0), 0))
+        .collect::<HashMap<(Xy, usize), i64>>();
+    let mut oldgrid1 = grid_coords_par_iter(1)
+        .map(|xy| ((xy, 1), cell_power_calc(xy, serial)))
+        .collect::<HashMap<(Xy, usize), i64>>();
+    let mut best: ((Xy, usize), i64) = oldgrid1
+        .par_iter()
+        .max_by_key(|(_, &v)| v)
+        .map(|(k, v)| (*k, *v))
+        .unwrap();
+    for size in 2..300 {
+        let newgrid = grid_coords_par_iter(size)
+            .filter(|xy| xy.0 <= 300 - size && xy.1 <= 300 - size)
+            .map(|xytl| {
+                let xytr = (xytl.0 + size - 1, xytl.1);
+                let xybl = (xytl.0, xytl.1 + size - 1);
+                let xydiag = (xytl.0 + 1, xytl.1 + 1);
+                (
+                    (xytl, size),
+                    oldgrid1.get(&(xytl, size - 1)).unwrap()
+                        + oldgrid1.get(&(xydiag, size - 1)).unwrap()
+                        + cell_power_calc(xytr, serial)
+                        + cell_power_calc(xybl, serial)
+                        - oldgrid2.get(&(xydiag, size - 2)).unwrap(),
+                )
+            })
+            .collect::<HashMap<(Xy, usize), i64>>();
+        best = newgrid.iter().fold(
+            best,
+            |best, (k, v)| if best.1 < *v { (*k, *v) } else { best },
+        );
+        // eprintln!("size {} best {:?}", size, best);
+        oldgrid2 = oldgrid1;
+        oldgrid1 = newgrid;
+    }
+    Ok(best.0)
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    assert_eq!(process_b("18\n".as_bytes())?, ((90, 269), 16));
+    assert_eq!(process_b("42\n".as_bytes())?, ((232, 251), 12));
     Ok(())
 }
@@ -4,8 +4,9 @@
 
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::io::BufRead;
 
-#[cfg(test)]
+use anyhow::anyhow;
 use anyhow::Result;
 
 pub const EXAMPLE: &str = "initial state: #..#.#..##......###...###
@@ -127,3 +128,78 @@ pub fn nextgen(state0: State, rules: &BTreeMap<Match, bool>) -> State {
         })
         .collect::<BTreeSet<i64>>()
 }
+
+pub fn process_a(bufin: impl BufRead) -> Result<i64> {
+    let input = parser::parse(bufin)?;
+    let (mut state, rules) = input;
+    let rules = rules.into_iter().collect::<BTreeMap<_, _>>();
+    for _ in 0..20 {
+        state = nextgen(state, &rules);
+    }
+    Ok(state.into_iter().sum())
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    assert_eq!(process_a(EXAMPLE.as_bytes())?, 325);
+    Ok(())
+}
+
+const GENERATIONS: i64 = 50000000000i64;
+
+/// Generations simulated waiting for the pattern to stop changing,
+/// other than moving
+const STABLE_MAX: i64 = 10000;
+
+pub fn process_b(bufin: impl BufRead) -> Result<i64> {
+    let input = parser::parse(bufin)?;
+    let (mut state, rules) = input;
+    let rules = rules.into_iter().collect::<BTreeMap<_, _>>();
+    let dead = |i| anyhow!("all plants died in generation {}", i);
+    let offset = *state.iter().min().ok_or_else(|| dead(0))?;
+    state = state.into_iter().map(|k| k - offset).collect();
+    let mut offsetaccum = offset;
+    for i in 0..STABLE_MAX {
+        let last = state.clone();
+        state = nextgen(state, &rules);
+        let offset = *state.iter().min().ok_or_else(|| dead(i + 1))?;
+        offsetaccum += offset;
+        state = state.into_iter().map(|k| k - offset).collect();
+        if state == last {
+            let len = state.len() as i64;
+            let overflow = || anyhow!("sum of the pots overflows");
+            let moved = offset
+                .checked_mul(len)
+                .and_then(|m| m.checked_mul(GENERATIONS - i - 1))
+                .ok_or_else(overflow)?;
+            return state
+                .iter()
+                .sum::<i64>()
+                .checked_add(offsetaccum.checked_mul(len).ok_or_else(overflow)?)
+                .and_then(|s| s.checked_add(moved))
+                .ok_or_else(overflow);
+        }
+    }
+    Err(anyhow!(
+        "pattern did not stabilize in {} generations",
+        STABLE_MAX
+    ))
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    assert_eq!(process_b(EXAMPLE.as_bytes())?, 999999999374i64);
+    // No rule makes a plant
+    let dying = "initial state: #..#\n\n#.... => .\n";
+    assert_eq!(
+        process_b(dying.as_bytes()).unwrap_err().to_string(),
+        "all plants died in generation 1"
+    );
+    // A glider that leaves a plant behind every other generation
+    let growing = "initial state: #\n\n....# => #\n...#. => #\n";
+    assert_eq!(
+        process_b(growing.as_bytes()).unwrap_err().to_string(),
+        "pattern did not stabilize in 10000 generations"
+    );
+    Ok(())
+}
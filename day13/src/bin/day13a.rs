@@ -3,43 +3,11 @@
 // file 'LICENSE', which is part of this source code package.
 
 use anyhow::Result;
-use std::collections::BTreeSet;
-use std::io::{stdin, BufRead};
+use std::io::stdin;
 
 use day13::*;
 
-pub const EXAMPLE: &str = r"/->-\        
-|   |  /----\
-| /-+--+-\  |
-| | |  | v  |
-\-+-/  \-+--/
-  \------/   
-";
-
-fn process(bufin: impl BufRead) -> Result<(u16, u16)> {
-    let (g, mut carts) = parser::parse(bufin)?;
-    loop {
-        carts.sort_by_key(|c| c.qa.tuple());
-        for c in &mut carts {
-            c.eval(&g)?;
-        }
-        let mut seen = BTreeSet::new();
-        for c in &carts {
-            if seen.contains(&c.qa) {
-                return Ok(c.qa.tuple());
-            }
-            seen.insert(c.qa);
-        }
-    }
-}
-
-#[test]
-fn test() -> Result<()> {
-    assert_eq!(process(EXAMPLE.as_bytes())?, (7, 3));
-    Ok(())
-}
-
 fn main() -> Result<()> {
-    println!("{:?}", process(stdin().lock())?);
+    println!("{:?}", process_a(stdin().lock())?);
     Ok(())
 }
@@ -2,7 +2,10 @@
 // This file is subject to the terms and conditions defined in
 // file 'LICENSE', which is part of this source code package.
 
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
 use std::fmt;
+use std::io::BufRead;
 
 use anyhow::anyhow;
 use anyhow::Result;
@@ -22,6 +25,15 @@ pub const EXAMPLE: &str = r"/->-\
   \------/   
 ";
 
+pub const EXAMPLE2: &str = r"/>-<\  
+|   |  
+| /<+-\
+| | | v
+\>+</ |
+  |   ^
+  \<->/
+";
+
 #[derive(Debug, Clone, Copy, Default)]
 pub enum Cell {
     #[default]
@@ -52,20 +64,21 @@ impl From<&Cell> for char {
     }
 }
 
-impl From<char> for Cell {
-    fn from(c: char) -> Cell {
+impl TryFrom<char> for Cell {
+    type Error = anyhow::Error;
+    fn try_from(c: char) -> Result<Cell> {
         match c {
-            ' ' => Cell::Empty,
-            '|' => Cell::Verti,
-            '-' => Cell::Horiz,
-            '/' => Cell::Raise,
-            '\\' => Cell::Fall,
-            '+' => Cell::Cross,
-            '^' => Cell::Verti,
-            'v' => Cell::Verti,
-            '>' => Cell::Horiz,
-            '<' => Cell::Horiz,
-            _ => panic!("could not convert char to cell"),
+            ' ' => Ok(Cell::Empty),
+            '|' => Ok(Cell::Verti),
+            '-' => Ok(Cell::Horiz),
+            '/' => Ok(Cell::Raise),
+            '\\' => Ok(Cell::Fall),
+            '+' => Ok(Cell::Cross),
+            '^' => Ok(Cell::Verti),
+            'v' => Ok(Cell::Verti),
+            '>' => Ok(Cell::Horiz),
+            '<' => Ok(Cell::Horiz),
+            _ => Err(anyhow!("could not convert {:?} to cell", c)),
         }
     }
 }
@@ -148,7 +161,7 @@ impl Cart {
                 self.lastdir = dir;
                 Ok(self.qr + dir)
             }
-            Cell::Empty => panic!("invalid cell"),
+            Cell::Empty => Err(anyhow!("cart off the tracks at {:?}", self.qa)),
         }?;
         self.qa = sqrid::qaqr_resolve(self.qa, qr)?;
         self.qr = qr;
@@ -156,6 +169,72 @@ impl Cart {
     }
 }
 
+/// Moves all carts in reading order, removing the ones that crash.
+/// Returns the positions of the crashes.
+pub fn tick(g: &Grid, carts: &mut Vec<Cart>) -> Result<Vec<Qa>> {
+    carts.sort_by_key(|c| {
+        let t = c.qa.tuple();
+        (t.1, t.0)
+    });
+    let mut crashes = vec![];
+    let mut dead = BTreeSet::new();
+    for i1 in 0..carts.len() {
+        let ri1 = Reverse(i1);
+        if dead.contains(&ri1) {
+            continue;
+        }
+        carts[i1].eval(g)?;
+        for (i2, c2) in carts.iter().enumerate() {
+            let ri2 = Reverse(i2);
+            if i1 == i2 || dead.contains(&ri2) {
+                continue;
+            }
+            if carts[i1].qa == c2.qa {
+                dead.insert(ri1);
+                dead.insert(ri2);
+                crashes.push(c2.qa);
+            }
+        }
+    }
+    for i in dead.into_iter() {
+        carts.remove(i.0);
+    }
+    Ok(crashes)
+}
+
+/// Detects when the carts get back to a state they were in before,
+/// which means they loop forever, using Brent's algorithm
+#[derive(Debug)]
+pub struct Cycle {
+    saved: Vec<Cart>,
+    power: usize,
+    steps: usize,
+}
+
+impl Cycle {
+    pub fn new(carts: &[Cart]) -> Cycle {
+        Cycle {
+            saved: carts.to_vec(),
+            power: 1,
+            steps: 0,
+        }
+    }
+
+    /// Returns true if the carts are in a state seen before
+    pub fn check(&mut self, carts: &[Cart]) -> bool {
+        if self.saved == carts {
+            return true;
+        }
+        self.steps += 1;
+        if self.steps == self.power {
+            self.saved = carts.to_vec();
+            self.power *= 2;
+            self.steps = 0;
+        }
+        false
+    }
+}
+
 pub fn qr_from_char(c: char) -> Option<Qr> {
     match c {
         '^' => Some(Qr::N),
@@ -183,8 +262,9 @@ pub mod parser {
     use super::Qr;
 
     pub fn cell(input: &str) -> IResult<&str, (Cell, Option<Qr>)> {
-        let (input, c) = character::one_of(" -|+\\/<>v^")(input)?;
-        Ok((input, (Cell::from(c), qr_from_char(c))))
+        combinator::map_res(character::anychar, |c| {
+            Cell::try_from(c).map(|cell| (cell, qr_from_char(c)))
+        })(input)
     }
 
     pub fn line(input: &str) -> IResult<&str, (Vec<Cell>, Vec<Option<Qr>>)> {
@@ -200,12 +280,28 @@ pub mod parser {
         bufin.read_to_string(&mut input)?;
         let (_, cellscarts) = combinator::all_consuming(multi::many1(line))(&input)
             .map_err(|e| anyhow!("error reading input: {:?}", e))?;
+        if cellscarts.len() > Qa::HEIGHT as usize {
+            return Err(anyhow!(
+                "grid has {} lines, the maximum is {}",
+                cellscarts.len(),
+                Qa::HEIGHT
+            ));
+        }
+        if let Some(width) = cellscarts
+            .iter()
+            .map(|(cells, _)| cells.len())
+            .find(|&w| w > Qa::WIDTH as usize)
+        {
+            return Err(anyhow!(
+                "grid line has {} columns, the maximum is {}",
+                width,
+                Qa::WIDTH
+            ));
+        }
         let grid = Qa::iter()
             .map(|qa| {
                 let t0 = qa.tuple();
                 let t = (t0.0 as usize, t0.1 as usize);
-                assert!(cellscarts.len() < Qa::HEIGHT as usize);
-                assert!(cellscarts[0].0.len() < Qa::WIDTH as usize);
                 if t.1 < cellscarts.len() && t.0 < cellscarts[t.1].0.len() {
                     cellscarts[t.1].0[t.0]
                 } else {
@@ -234,6 +330,8 @@ pub mod parser {
 
 #[test]
 fn test() -> Result<()> {
+    assert!(parser::parse("-x-\n".as_bytes()).is_err());
+    assert!(parser::parse(format!("{}\n", "-".repeat(200)).as_bytes()).is_err());
     let (g, c) = parser::parse(EXAMPLE.as_bytes())?;
     eprintln!("{}", gridcarts(&g, &c));
     assert_eq!(
@@ -253,3 +351,52 @@ fn test() -> Result<()> {
     );
     Ok(())
 }
+
+pub fn process_a(bufin: impl BufRead) -> Result<(u16, u16)> {
+    let (g, mut carts) = parser::parse(bufin)?;
+    let mut cycle = Cycle::new(&carts);
+    loop {
+        if let Some(qa) = tick(&g, &mut carts)?.first() {
+            return Ok(qa.tuple());
+        }
+        if cycle.check(&carts) {
+            return Err(anyhow!("carts never crash"));
+        }
+    }
+}
+
+#[test]
+fn test_a() -> Result<()> {
+    assert_eq!(process_a(EXAMPLE.as_bytes())?, (7, 3));
+    // Carts move one at a time, in reading order, so the first one
+    // crashes into the second before they can swap places
+    assert_eq!(process_a("-><-\n".as_bytes())?, (2, 0));
+    // The first crash in reading order is reported, not the one with
+    // the smallest x
+    assert_eq!(process_a("--->-<\n>-<---\n".as_bytes())?, (4, 0));
+    assert!(process_a("/>\\\n\\-/\n".as_bytes()).is_err());
+    Ok(())
+}
+
+pub fn process_b(bufin: impl BufRead) -> Result<(u16, u16)> {
+    let (g, mut carts) = parser::parse(bufin)?;
+    let mut cycle = Cycle::new(&carts);
+    while carts.len() > 1 {
+        tick(&g, &mut carts)?;
+        if cycle.check(&carts) {
+            return Err(anyhow!("carts never crash"));
+        }
+    }
+    Ok(carts
+        .first()
+        .ok_or_else(|| anyhow!("no cart left"))?
+        .qa
+        .tuple())
+}
+
+#[test]
+fn test_b() -> Result<()> {
+    assert_eq!(process_b(EXAMPLE2.as_bytes())?, (6, 4));
+    assert!(process_b("->-<-\n".as_bytes()).is_err());
+    Ok(())
+}
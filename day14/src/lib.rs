@@ -53,3 +53,85 @@ impl fmt::Display for State {
         Ok(())
     }
 }
+
+pub fn process_a(ignore: u64) -> Result<u64> {
+    let mut st = State::default();
+    while (st.recipes.len() as u64) < ignore + 10_u64 {
+        st.process();
+    }
+    let mut num = 0_u64;
+    for i in 0..10 {
+        num = num * 10 + st.recipes[ignore as usize + i] as u64;
+    }
+    Ok(num)
+}
+
+#[test]
+fn test_a5() -> Result<()> {
+    assert_eq!(process_a(5)?, 124515891);
+    Ok(())
+}
+
+#[test]
+fn test_a9() -> Result<()> {
+    assert_eq!(process_a(9)?, 5158916779);
+    Ok(())
+}
+
+#[test]
+fn test_a18() -> Result<()> {
+    assert_eq!(process_a(18)?, 9251071085);
+    Ok(())
+}
+
+#[test]
+fn test_a2018() -> Result<()> {
+    assert_eq!(process_a(2018)?, 5941429882);
+    Ok(())
+}
+
+fn has_suffix(suffix: &[u8], st: &State) -> Option<usize> {
+    let slen = suffix.len();
+    let rlen = st.recipes.len();
+    for i in 0..2 {
+        if rlen >= slen + i && &st.recipes[(rlen - slen - i)..(rlen - i)] == suffix {
+            return Some(rlen - slen - i);
+        }
+    }
+    None
+}
+
+pub fn process_b(suffix: &[u8]) -> Result<usize> {
+    let mut st = State::default();
+    let suffix = suffix.iter().map(|c| c - b'0').collect::<Vec<u8>>();
+    Ok(loop {
+        if let Some(result) = has_suffix(&suffix, &st) {
+            break result;
+        }
+        st.process();
+    })
+}
+
+#[test]
+fn test_b5() -> Result<()> {
+    assert_eq!(process_b(b"01245")?, 5);
+    Ok(())
+}
+
+#[test]
+fn test_b9() -> Result<()> {
+    assert_eq!(process_b(b"51589")?, 9);
+    Ok(())
+}
+
+#[test]
+fn test_b18() -> Result<()> {
+    assert_eq!(process_b(b"92510")?, 18);
+    Ok(())
+}
+
+#[test]
+fn test_b2018() -> Result<()> {
+    assert_eq!(process_b(b"59414")?, 2018);
+    Ok(())
+}
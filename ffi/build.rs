@@ -0,0 +1,20 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+//! Generates the C header in OUT_DIR; tests/header.rs checks that the
+//! one in include/ is up to date
+
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    let dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(dir.join("cbindgen.toml")).unwrap();
+    cbindgen::generate_with_config(&dir, config)
+        .expect("error generating the C header")
+        .write_to_file(out.join("aoc2018.h"));
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
@@ -0,0 +1,87 @@
+/* Generated by cbindgen from ffi/src/lib.rs, do not edit */
+
+#ifndef AOC2018_H
+#define AOC2018_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/*
+ Outcome of aoc_solve
+ */
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  /*
+   The input pointer is NULL but the length is not 0
+   */
+  AOC_STATUS_NULL_INPUT,
+  /*
+   The input is not valid UTF-8
+   */
+  AOC_STATUS_INVALID_UTF8,
+  /*
+   There is no solver for the given day and part
+   */
+  AOC_STATUS_UNKNOWN_SOLVER,
+  /*
+   The solver returned an error, usually because of invalid input
+   */
+  AOC_STATUS_FAILED,
+  /*
+   The solver panicked
+   */
+  AOC_STATUS_PANICKED,
+} AocStatus;
+
+/*
+ Result of aoc_solve, owned by the caller and released with
+ aoc_result_free
+ */
+typedef struct AocResult {
+  enum AocStatus status;
+  /*
+   The answer, NULL if status is not AOC_STATUS_OK
+   */
+  char *answer;
+  /*
+   Error message, NULL if status is AOC_STATUS_OK
+   */
+  char *error;
+} AocResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Solves `part` ('a' or 'b') of `day` with the `len` bytes of input
+ at `input`. The result must be released with aoc_result_free.
+
+ # Safety
+
+ `input` must be NULL or point to at least `len` readable bytes.
+ */
+struct AocResult aoc_solve(uint32_t day, char part, const uint8_t *input, uintptr_t len);
+
+/*
+ Releases the strings of a result returned by aoc_solve and sets
+ them to NULL; freeing the same result twice is harmless.
+
+ # Safety
+
+ `result` must be NULL or point to a result returned by aoc_solve.
+ */
+void aoc_result_free(struct AocResult *result);
+
+/*
+ Static name of a status, as in "AOC_STATUS_OK"
+ */
+const char *aoc_status_name(enum AocStatus status);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC2018_H */
@@ -0,0 +1,181 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+//! C interface to the solvers, see include/aoc2018.h
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic;
+use std::ptr;
+use std::slice;
+
+use solvers::Day;
+
+/// Outcome of aoc_solve
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AocStatus {
+    Ok = 0,
+    /// The input pointer is NULL but the length is not 0
+    NullInput,
+    /// The input is not valid UTF-8
+    InvalidUtf8,
+    /// There is no solver for the given day and part
+    UnknownSolver,
+    /// The solver returned an error, usually because of invalid input
+    Failed,
+    /// The solver panicked
+    Panicked,
+}
+
+/// Result of aoc_solve, owned by the caller and released with
+/// aoc_result_free
+#[repr(C)]
+pub struct AocResult {
+    pub status: AocStatus,
+    /// The answer, NULL if status is not AOC_STATUS_OK
+    pub answer: *mut c_char,
+    /// Error message, NULL if status is AOC_STATUS_OK
+    pub error: *mut c_char,
+}
+
+fn cstring(s: String) -> *mut c_char {
+    // Messages with NUL bytes are truncated instead of lost
+    let s = match CString::new(s) {
+        Ok(s) => s,
+        Err(e) => {
+            let nul = e.nul_position();
+            let mut bytes = e.into_vec();
+            bytes.truncate(nul);
+            CString::new(bytes).unwrap()
+        }
+    };
+    s.into_raw()
+}
+
+fn result(answer: Result<String, (AocStatus, String)>) -> AocResult {
+    match answer {
+        Ok(answer) => AocResult {
+            status: AocStatus::Ok,
+            answer: cstring(answer),
+            error: ptr::null_mut(),
+        },
+        Err((status, error)) => AocResult {
+            status,
+            answer: ptr::null_mut(),
+            error: cstring(error),
+        },
+    }
+}
+
+fn solve(day: u32, part: c_char, input: &[u8]) -> Result<String, (AocStatus, String)> {
+    let day = u8::try_from(day)
+        .ok()
+        .map(Day)
+        .ok_or_else(|| (AocStatus::UnknownSolver, format!("invalid day {}", day)))?;
+    let part = char::from(part as u8);
+    let solver =
+        solvers::solver(day, part).map_err(|e| (AocStatus::UnknownSolver, e.to_string()))?;
+    let input = std::str::from_utf8(input).map_err(|e| (AocStatus::InvalidUtf8, e.to_string()))?;
+    match panic::catch_unwind(|| (solver.solve)(input)) {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(e)) => Err((AocStatus::Failed, e.to_string())),
+        Err(e) => {
+            let msg = e
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| e.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            Err((AocStatus::Panicked, msg))
+        }
+    }
+}
+
+/// Solves `part` ('a' or 'b') of `day` with the `len` bytes of input
+/// at `input`. The result must be released with aoc_result_free.
+///
+/// # Safety
+///
+/// `input` must be NULL or point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: c_char,
+    input: *const u8,
+    len: usize,
+) -> AocResult {
+    let input = if input.is_null() {
+        if len != 0 {
+            return result(Err((AocStatus::NullInput, "input is NULL".to_string())));
+        }
+        &[][..]
+    } else {
+        slice::from_raw_parts(input, len)
+    };
+    result(solve(day, part, input))
+}
+
+/// Releases the strings of a result returned by aoc_solve and sets
+/// them to NULL; freeing the same result twice is harmless.
+///
+/// # Safety
+///
+/// `result` must be NULL or point to a result returned by aoc_solve.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_result_free(result: *mut AocResult) {
+    let Some(result) = result.as_mut() else {
+        return;
+    };
+    for s in [&mut result.answer, &mut result.error] {
+        if !s.is_null() {
+            drop(CString::from_raw(*s));
+            *s = ptr::null_mut();
+        }
+    }
+}
+
+/// Static name of a status, as in "AOC_STATUS_OK"
+#[no_mangle]
+pub extern "C" fn aoc_status_name(status: AocStatus) -> *const c_char {
+    let name: &'static CStr = match status {
+        AocStatus::Ok => c"AOC_STATUS_OK",
+        AocStatus::NullInput => c"AOC_STATUS_NULL_INPUT",
+        AocStatus::InvalidUtf8 => c"AOC_STATUS_INVALID_UTF8",
+        AocStatus::UnknownSolver => c"AOC_STATUS_UNKNOWN_SOLVER",
+        AocStatus::Failed => c"AOC_STATUS_FAILED",
+        AocStatus::Panicked => c"AOC_STATUS_PANICKED",
+    };
+    name.as_ptr()
+}
+
+#[cfg(test)]
+fn solve_str(day: u32, part: u8, input: &str) -> (AocStatus, Option<String>, Option<String>) {
+    let mut r = unsafe { aoc_solve(day, part as c_char, input.as_ptr(), input.len()) };
+    let get = |p: *mut c_char| unsafe {
+        p.as_ref()
+            .map(|p| CStr::from_ptr(p).to_string_lossy().into_owned())
+    };
+    let ans = (r.status, get(r.answer), get(r.error));
+    unsafe { aoc_result_free(&mut r) };
+    assert!(r.answer.is_null() && r.error.is_null());
+    ans
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        solve_str(8, b'b', "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2\n"),
+        (AocStatus::Ok, Some("66".to_string()), None)
+    );
+    let (status, _, error) = solve_str(8, b'c', "");
+    assert_eq!(status, AocStatus::UnknownSolver);
+    assert_eq!(error.as_deref(), Some("no solver for day08c"));
+    assert_eq!(solve_str(1, b'a', "+1\nx\n").0, AocStatus::Failed);
+    assert_eq!(solve_str(300, b'a', "").0, AocStatus::UnknownSolver);
+    let mut r = unsafe { aoc_solve(1, b'a' as c_char, ptr::null(), 3) };
+    assert_eq!(r.status, AocStatus::NullInput);
+    unsafe { aoc_result_free(&mut r) };
+    let mut r = unsafe { aoc_solve(1, b'a' as c_char, [0xff].as_ptr(), 1) };
+    assert_eq!(r.status, AocStatus::InvalidUtf8);
+    unsafe { aoc_result_free(&mut r) };
+}
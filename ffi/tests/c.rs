@@ -0,0 +1,48 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+//! Builds tests/c/test.c against the generated header and the cdylib,
+//! and runs it
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[test]
+fn test_c() {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // The test binary is in target/<profile>/deps, the cdylib one level up
+    let exe = env::current_exe().unwrap();
+    let libdir = exe.parent().unwrap().parent().unwrap().to_path_buf();
+    assert!(
+        libdir.join("libaoc2018.so").exists(),
+        "no libaoc2018.so in {}",
+        libdir.display()
+    );
+    let out = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("test_c");
+    let cc = Command::new("cc")
+        .arg(manifest.join("tests/c/test.c"))
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(manifest.join("include"))
+        .arg("-L")
+        .arg(&libdir)
+        .arg("-laoc2018")
+        .arg("-o")
+        .arg(&out)
+        .status()
+        .expect("running cc");
+    assert!(cc.success(), "error compiling tests/c/test.c");
+    let run = Command::new(&out)
+        .env("LD_LIBRARY_PATH", &libdir)
+        .output()
+        .unwrap();
+    assert!(
+        run.status.success(),
+        "{}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "ok\n");
+}
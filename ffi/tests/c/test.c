@@ -0,0 +1,61 @@
+/* Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+ * This file is subject to the terms and conditions defined in
+ * file 'LICENSE', which is part of this source code package. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc2018.h"
+
+static int failures = 0;
+
+static void check(uint32_t day, char part, const char *input, AocStatus status,
+                  const char *answer) {
+    AocResult r = aoc_solve(day, part, (const uint8_t *)input, strlen(input));
+    if (r.status != status) {
+        fprintf(stderr, "day%02u%c: expected %s, got %s (%s)\n", day, part,
+                aoc_status_name(status), aoc_status_name(r.status),
+                r.error ? r.error : "");
+        failures++;
+    } else if (answer && (!r.answer || strcmp(r.answer, answer) != 0)) {
+        fprintf(stderr, "day%02u%c: expected %s, got %s\n", day, part, answer,
+                r.answer ? r.answer : "NULL");
+        failures++;
+    } else if ((status == AOC_STATUS_OK) != (r.error == NULL)) {
+        fprintf(stderr, "day%02u%c: error message mismatch\n", day, part);
+        failures++;
+    }
+    aoc_result_free(&r);
+    aoc_result_free(&r);
+}
+
+int main(void) {
+    check(1, 'a', "+1\n-2\n+3\n+1\n", AOC_STATUS_OK, "3");
+    check(7, 'a',
+          "Step C must be finished before step A can begin.\n"
+          "Step C must be finished before step F can begin.\n"
+          "Step A must be finished before step B can begin.\n"
+          "Step A must be finished before step D can begin.\n"
+          "Step B must be finished before step E can begin.\n"
+          "Step D must be finished before step E can begin.\n"
+          "Step F must be finished before step E can begin.\n",
+          AOC_STATUS_OK, "CABDFE");
+    check(8, 'b', "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2\n", AOC_STATUS_OK,
+          "66");
+    check(14, 'b', "59414", AOC_STATUS_OK, "2018");
+    check(8, 'c', "", AOC_STATUS_UNKNOWN_SOLVER, NULL);
+    check(26, 'a', "", AOC_STATUS_UNKNOWN_SOLVER, NULL);
+    check(3, 'a', "bogus", AOC_STATUS_FAILED, NULL);
+    check(1, 'a', "\xff", AOC_STATUS_INVALID_UTF8, NULL);
+    AocResult r = aoc_solve(1, 'a', NULL, 3);
+    if (r.status != AOC_STATUS_NULL_INPUT) {
+        fprintf(stderr, "NULL input: got %s\n", aoc_status_name(r.status));
+        failures++;
+    }
+    aoc_result_free(&r);
+    aoc_result_free(NULL);
+    if (failures == 0) {
+        printf("ok\n");
+    }
+    return failures == 0 ? 0 : 1;
+}
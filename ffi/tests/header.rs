@@ -0,0 +1,17 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+//! Checks that the committed header matches the one generated by the
+//! build script from src/lib.rs
+
+#[test]
+fn test_header() {
+    let generated = concat!(env!("OUT_DIR"), "/aoc2018.h");
+    assert!(
+        include_str!("../include/aoc2018.h")
+            == include_str!(concat!(env!("OUT_DIR"), "/aoc2018.h")),
+        "include/aoc2018.h is out of date, copy {} over it",
+        generated
+    );
+}
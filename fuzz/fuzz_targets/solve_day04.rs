@@ -0,0 +1,23 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+/// The solvers visit every minute of every sleep
+const SPAN_MAX_DAYS: i64 = 31;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(entries) = day04::parser::parse(data) {
+        let first = entries.iter().map(|e| e.when).min();
+        let last = entries.iter().map(|e| e.when).max();
+        if let (Some(first), Some(last)) = (first, last) {
+            if (last - first).num_days() <= SPAN_MAX_DAYS {
+                let _ = day04::process_a(data);
+                let _ = day04::process_b(data);
+            }
+        }
+    }
+});
@@ -0,0 +1,14 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if day05::parser::parse(data).is_ok() {
+        let _ = day05::process_a(data);
+        let _ = day05::process_b(data);
+    }
+});
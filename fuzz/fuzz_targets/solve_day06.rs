@@ -0,0 +1,21 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+/// The solvers label every cell of the bounding box
+const SIDE_MAX: i64 = 200;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(centers) = day06::parser::parse(data) {
+        if let Some((min, max)) = day06::bounding_box(&centers) {
+            if max.0 - min.0 <= SIDE_MAX && max.1 - min.1 <= SIDE_MAX {
+                let _ = day06::process_a(data);
+                let _ = day06::process_b(32, data);
+            }
+        }
+    }
+});
@@ -0,0 +1,21 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+/// The solvers allocate the scores of all players and the links of
+/// all marbles, and process_b plays 100 times the marbles of the input
+const PLAYERS_MAX: usize = 1000;
+const MARBLES_MAX: usize = 1000;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((players, marbles)) = day09::parser::parse(data) {
+        if players <= PLAYERS_MAX && marbles <= MARBLES_MAX {
+            let _ = day09::process_a(data);
+            let _ = day09::process_b(data);
+        }
+    }
+});
@@ -0,0 +1,46 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+//! Seeds the corpus of each fuzz target with the examples of its day.
+//! Run from the fuzz directory: `cargo run --bin seed`
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const EXAMPLES: &[(&str, &[&str])] = &[
+    ("day01", &[day01::EXAMPLE]),
+    ("day02", &[day02::EXAMPLE1]),
+    ("day03", &[day03::EXAMPLE]),
+    ("day04", &[day04::EXAMPLE]),
+    ("day05", &[day05::EXAMPLE]),
+    ("day06", &[day06::EXAMPLE]),
+    ("day07", &[day07::EXAMPLE]),
+    ("day08", &[day08::EXAMPLE]),
+    ("day09", &[day09::EXAMPLE]),
+    ("day10", &[day10::EXAMPLE]),
+    ("day11", &[day11::EXAMPLE]),
+    ("day12", &[day12::EXAMPLE]),
+    ("day13", &[day13::EXAMPLE, day13::EXAMPLE2]),
+];
+
+fn main() -> io::Result<()> {
+    for (day, examples) in EXAMPLES {
+        for kind in ["parse", "solve"] {
+            let target = format!("{}_{}", kind, day);
+            if !Path::new("fuzz_targets")
+                .join(format!("{}.rs", target))
+                .exists()
+            {
+                continue;
+            }
+            let dir = Path::new("corpus").join(&target);
+            fs::create_dir_all(&dir)?;
+            for (i, example) in examples.iter().enumerate() {
+                fs::write(dir.join(format!("example{}", i + 1)), example)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use runner::*;
+
+#[derive(Parser, Debug)]
+#[command(about = "Advent of Code 2018 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Explore the parsed state of a day interactively
+    Repl {
+        /// Day to load, as in 8 or day08
+        day: Day,
+        /// Input file; the puzzle example is used if omitted
+        input: Option<PathBuf>,
+    },
+    /// Watch the simulation of a day in a full-screen viewer
+    ///
+    /// Keys: space play/pause, n step, arrows or hjkl scroll,
+    /// +/- zoom, 0 fit to screen, [/] slower/faster, q quit.
+    View {
+        /// Day to load, as in 13 or day13
+        day: Day,
+        /// Input file; the puzzle example is used if omitted
+        input: Option<PathBuf>,
+    },
+    /// Print an analysis of the input of a day, beyond the answers
+    Report {
+        /// Day to load, as in 1 or day01
+        day: Day,
+        /// Input file; the puzzle example is used if omitted
+        input: Option<PathBuf>,
+        /// Output format: text, json, dot (graphs only) or csv (tables only)
+        #[arg(short, long, default_value = "text")]
+        format: report::Format,
+        /// Passes through the input to consider (day01)
+        #[arg(long, default_value_t = 10)]
+        passes: usize,
+        /// Time of day observed, as in 23:00-01:00 or 24h (day04)
+        #[arg(long, default_value = "00:00-01:00")]
+        window: day04::Window,
+        /// Cells shown in the map, as in -10,-10:60,40 (day06)
+        #[arg(long, allow_hyphen_values = true)]
+        viewport: Option<day06::Viewport>,
+    },
+    /// Serve a web page that runs the solvers and streams simulations
+    Serve {
+        /// Port to listen on, always bound to localhost
+        #[arg(short, long, default_value_t = 8018)]
+        port: u16,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Repl { day, input } => {
+            let input = day.input(input.as_deref())?;
+            repl::run(day, input)
+        }
+        Command::View { day, input } => {
+            let input = day.input(input.as_deref())?;
+            view::run(day, &input)
+        }
+        Command::Report {
+            day,
+            input,
+            format,
+            passes,
+            window,
+            viewport,
+        } => {
+            let input = day.input(input.as_deref())?;
+            let options = report::Options {
+                passes,
+                window,
+                viewport,
+            };
+            report::run(day, &input, &options, format)
+        }
+        Command::Serve { port } => serve::run(port),
+    }
+}
@@ -0,0 +1,11 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+pub mod repl;
+pub mod report;
+pub mod serve;
+pub mod view;
+
+pub use solvers;
+pub use solvers::Day;
@@ -0,0 +1,184 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+use rustyline::error::ReadlineError;
+use std::fs;
+
+use super::Day;
+
+mod day03;
+mod day04;
+mod day08;
+mod day09;
+mod day10;
+mod day12;
+mod day13;
+
+pub const HELP: &str = "Commands:
+  step [N]      advance the state N steps (default 1)
+  show [ARGS]   display the current state
+  query ARGS    ask a day-specific question
+  reset         parse the input again, dropping all changes
+  save FILE     write a snapshot of the current state to FILE
+  history       list the commands entered so far
+  help          show this help
+  quit          leave the repl
+";
+
+/// The parsed state of a day, as exposed by the repl
+pub trait Session {
+    /// Day-specific help for the step, show and query commands
+    fn help(&self) -> &'static str;
+
+    fn step(&mut self, _n: usize) -> Result<String> {
+        Err(anyhow!("this day has nothing to step"))
+    }
+
+    fn show(&self, args: &[&str]) -> Result<String>;
+
+    fn query(&self, args: &[&str]) -> Result<String>;
+
+    /// Full textual dump of the current state, used by `save`
+    fn snapshot(&self) -> Result<String> {
+        self.show(&[])
+    }
+}
+
+pub fn session(day: Day, input: &str) -> Result<Box<dyn Session>> {
+    Ok(match day.0 {
+        3 => Box::new(day03::Day03::new(input)?),
+        4 => Box::new(day04::Day04::new(input)?),
+        8 => Box::new(day08::Day08::new(input)?),
+        9 => Box::new(day09::Day09::new(input)?),
+        10 => Box::new(day10::Day10::new(input)?),
+        12 => Box::new(day12::Day12::new(input)?),
+        13 => Box::new(day13::Day13::new(input)?),
+        _ => return Err(anyhow!("{} is not supported by the repl", day)),
+    })
+}
+
+pub enum Flow {
+    Continue(String),
+    Quit,
+}
+
+pub struct Repl {
+    day: Day,
+    input: String,
+    session: Box<dyn Session>,
+    history: Vec<String>,
+}
+
+impl Repl {
+    pub fn new(day: Day, input: String) -> Result<Repl> {
+        let session = session(day, &input)?;
+        Ok(Repl {
+            day,
+            input,
+            session,
+            history: vec![],
+        })
+    }
+
+    /// Executes a single command line
+    pub fn exec(&mut self, line: &str) -> Result<Flow> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let Some((&cmd, args)) = words.split_first() else {
+            return Ok(Flow::Continue(String::new()));
+        };
+        self.history.push(line.trim().to_string());
+        let output = match cmd {
+            "step" => {
+                let n = match args {
+                    [] => 1,
+                    [n] => n.parse()?,
+                    _ => return Err(anyhow!("usage: step [N]")),
+                };
+                self.session.step(n)?
+            }
+            "show" => self.session.show(args)?,
+            "query" => self.session.query(args)?,
+            "reset" => {
+                self.session = session(self.day, &self.input)?;
+                format!("{} state reset", self.day)
+            }
+            "save" => {
+                let [filename] = args else {
+                    return Err(anyhow!("usage: save FILE"));
+                };
+                fs::write(filename, self.session.snapshot()?)?;
+                format!("snapshot saved to {}", filename)
+            }
+            "history" => self
+                .history
+                .iter()
+                .enumerate()
+                .map(|(i, l)| format!("{:4}  {}\n", i + 1, l))
+                .collect(),
+            "help" => format!("{}\n{}", HELP, self.session.help()),
+            "quit" | "exit" => return Ok(Flow::Quit),
+            _ => return Err(anyhow!("unknown command {:?}, try help", cmd)),
+        };
+        Ok(Flow::Continue(output))
+    }
+}
+
+pub fn run(day: Day, input: String) -> Result<()> {
+    let mut repl = Repl::new(day, input)?;
+    let mut editor = rustyline::DefaultEditor::new()?;
+    let prompt = format!("{}> ", day);
+    loop {
+        let line = match editor.readline(&prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        if !line.trim().is_empty() {
+            editor.add_history_entry(line.as_str())?;
+        }
+        match repl.exec(&line) {
+            Ok(Flow::Continue(output)) => {
+                print!("{}", output);
+                if !output.is_empty() && !output.ends_with('\n') {
+                    println!();
+                }
+            }
+            Ok(Flow::Quit) => break,
+            Err(e) => eprintln!("error: {}", e),
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test() -> Result<()> {
+    let mut repl = Repl::new(Day(9), ::day09::EXAMPLE.to_string())?;
+    let Flow::Continue(out) = repl.exec("step 25")? else {
+        panic!("unexpected quit");
+    };
+    assert_eq!(out, "turn 25");
+    let Flow::Continue(out) = repl.exec("query score")? else {
+        panic!("unexpected quit");
+    };
+    assert_eq!(out, "32");
+    repl.exec("reset")?;
+    let Flow::Continue(out) = repl.exec("query score")? else {
+        panic!("unexpected quit");
+    };
+    assert_eq!(out, "0");
+    let Flow::Continue(out) = repl.exec("history")? else {
+        panic!("unexpected quit");
+    };
+    assert_eq!(
+        out,
+        "   1  step 25\n   2  query score\n   3  reset\n   4  query score\n   5  history\n"
+    );
+    assert!(repl.exec("bogus").is_err());
+    assert!(matches!(repl.exec("quit")?, Flow::Quit));
+    assert!(session(Day(1), "").is_err());
+    Ok(())
+}
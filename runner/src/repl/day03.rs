@@ -0,0 +1,104 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+
+use day03::*;
+
+pub struct Day03 {
+    index: ClaimIndex,
+}
+
+impl Day03 {
+    pub fn new(input: &str) -> Result<Day03> {
+        Ok(Day03 {
+            index: ClaimIndex::new(parser::parse(input.as_bytes())?),
+        })
+    }
+}
+
+fn claim_line(c: &Claim) -> String {
+    format!(
+        "#{} @ {},{}: {}x{}\n",
+        c.id,
+        c.rect.x0,
+        c.rect.y0,
+        c.rect.width(),
+        c.rect.height()
+    )
+}
+
+fn num(arg: &str) -> Result<i64> {
+    arg.parse().map_err(|_| anyhow!("invalid number {:?}", arg))
+}
+
+impl super::Session for Day03 {
+    fn help(&self) -> &'static str {
+        "day03:
+  show [ID]                 all claims, or claim ID
+  query at X Y              claims that cover the square inch at X,Y
+  query rect X Y W H        claims that share area with the rectangle
+  query overlap K           claims that overlap at least K others
+"
+    }
+
+    fn show(&self, args: &[&str]) -> Result<String> {
+        let claims = self.index.claims();
+        match args {
+            [] => Ok(claims.iter().map(claim_line).collect()),
+            [id] => {
+                let id = id.parse::<usize>()?;
+                claims
+                    .iter()
+                    .find(|c| c.id == id)
+                    .map(claim_line)
+                    .ok_or_else(|| anyhow!("claim {} not found", id))
+            }
+            _ => Err(anyhow!("usage: show [ID]")),
+        }
+    }
+
+    fn query(&self, args: &[&str]) -> Result<String> {
+        let found = match args {
+            ["at", x, y] => self.index.at((num(x)?, num(y)?)),
+            ["rect", x, y, w, h] => {
+                let rect = Rect::new((num(x)?, num(y)?), (num(w)?, num(h)?));
+                self.index.intersecting(&rect)
+            }
+            ["overlap", k] => {
+                return Ok(self
+                    .index
+                    .k_overlap(k.parse()?)
+                    .into_iter()
+                    .map(|(c, n)| format!("#{} overlaps {}\n", c.id, n))
+                    .collect())
+            }
+            _ => {
+                return Err(anyhow!(
+                    "usage: query at X Y | query rect X Y W H | query overlap K"
+                ))
+            }
+        };
+        Ok(found.into_iter().map(claim_line).collect())
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::Session;
+    let s = Day03::new(EXAMPLE)?;
+    assert_eq!(s.show(&["3"])?, "#3 @ 5,5: 2x2\n");
+    assert_eq!(
+        s.query(&["at", "4", "4"])?,
+        "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n"
+    );
+    assert_eq!(s.query(&["rect", "6", "6", "9", "9"])?, "#3 @ 5,5: 2x2\n");
+    assert_eq!(
+        s.query(&["overlap", "1"])?,
+        "#1 overlaps 1\n#2 overlaps 1\n"
+    );
+    assert!(s.query(&["at", "x", "1"]).is_err());
+    Ok(())
+}
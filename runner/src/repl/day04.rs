@@ -0,0 +1,86 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+
+use day04::*;
+
+pub struct Day04 {
+    entries: Vec<Entry>,
+    log: ShiftLog,
+}
+
+impl Day04 {
+    pub fn new(input: &str) -> Result<Day04> {
+        let mut entries = parser::parse(input.as_bytes())?;
+        entries.sort();
+        let log = ShiftLog::new(entries.clone())?;
+        Ok(Day04 { entries, log })
+    }
+}
+
+impl super::Session for Day04 {
+    fn help(&self) -> &'static str {
+        "day04:
+  show [DATE]     log entries, optionally only the ones of DATE (YYYY-MM-DD)
+  query guards    total minutes asleep of each guard
+  query guard ID  minute histogram of guard ID
+"
+    }
+
+    fn show(&self, args: &[&str]) -> Result<String> {
+        let date = match args {
+            [] => None,
+            [date] => Some(date.parse::<chrono::NaiveDate>()?),
+            _ => return Err(anyhow!("usage: show [DATE]")),
+        };
+        Ok(self
+            .entries
+            .iter()
+            .filter(|e| date.map(|d| e.when.date() == d).unwrap_or(true))
+            .map(|e| format!("{}\n", e))
+            .collect())
+    }
+
+    fn query(&self, args: &[&str]) -> Result<String> {
+        let stats = SleepStats::new(&self.log, &Window::default());
+        match args {
+            ["guards"] => Ok(stats
+                .guards
+                .iter()
+                .map(|g| format!("#{}: {}\n", g.guard, g.total()))
+                .collect()),
+            ["guard", id] => {
+                let id = id.trim_start_matches('#').parse::<Guard>()?;
+                let g = stats
+                    .get(id)
+                    .filter(|g| g.total() > 0)
+                    .ok_or_else(|| anyhow!("guard #{} never sleeps", id))?;
+                Ok(g.histogram
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &n)| n > 0)
+                    .map(|(i, &n)| {
+                        let time = g.window.time(i).format("%H:%M");
+                        format!("{} {:3} {}\n", time, n, "#".repeat(n))
+                    })
+                    .collect())
+            }
+            _ => Err(anyhow!("usage: query guards | query guard ID")),
+        }
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::Session;
+    let mut s = Day04::new(EXAMPLE)?;
+    assert_eq!(s.query(&["guards"])?, "#10: 50\n#99: 30\n");
+    let hist = s.query(&["guard", "#10"])?;
+    assert!(hist.contains("00:24   2 ##\n"));
+    assert_eq!(s.show(&["1518-11-03"])?.lines().count(), 3);
+    assert!(s.step(1).is_err());
+    Ok(())
+}
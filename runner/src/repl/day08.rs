@@ -0,0 +1,112 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+use std::fmt::Write;
+
+use day08::*;
+
+pub struct Day08 {
+    nodes: Vec<Node>,
+}
+
+impl Day08 {
+    pub fn new(input: &str) -> Result<Day08> {
+        Ok(Day08 {
+            nodes: parser::parse(input.as_bytes())?,
+        })
+    }
+
+    fn inode(&self, arg: &str) -> Result<Inode> {
+        let i = arg.parse::<usize>()?;
+        if i >= self.nodes.len() {
+            return Err(anyhow!(
+                "node {} not found, tree has {}",
+                i,
+                self.nodes.len()
+            ));
+        }
+        Ok(Inode::try_from(i)?)
+    }
+
+    fn tree(&self, out: &mut String, inode: Inode, depth: usize) {
+        let node = &self.nodes[usize::from(inode)];
+        writeln!(
+            out,
+            "{:indent$}{} {:?} value {}",
+            "",
+            usize::from(inode),
+            node.data,
+            node_value(&self.nodes, inode),
+            indent = 2 * depth
+        )
+        .unwrap();
+        for &child in &node.children {
+            self.tree(out, child, depth + 1);
+        }
+    }
+}
+
+impl super::Session for Day08 {
+    fn help(&self) -> &'static str {
+        "day08:
+  show [ID]        tree of nodes under ID (default: the root)
+  query node ID    children and metadata of node ID
+  query value ID   value of node ID
+  query metadata   sum of all metadata entries
+"
+    }
+
+    fn show(&self, args: &[&str]) -> Result<String> {
+        let inode = match args {
+            [] => Inode::FIRST,
+            [id] => self.inode(id)?,
+            _ => return Err(anyhow!("usage: show [ID]")),
+        };
+        let mut out = String::new();
+        self.tree(&mut out, inode, 0);
+        Ok(out)
+    }
+
+    fn query(&self, args: &[&str]) -> Result<String> {
+        match args {
+            ["node", id] => {
+                let node = &self.nodes[usize::from(self.inode(id)?)];
+                let children = node.children.iter().map(|&c| usize::from(c));
+                Ok(format!(
+                    "children {:?}\nmetadata {:?}\n",
+                    children.collect::<Vec<_>>(),
+                    node.data
+                ))
+            }
+            ["value", id] => Ok(node_value(&self.nodes, self.inode(id)?).to_string()),
+            ["metadata"] => Ok(self
+                .nodes
+                .iter()
+                .flat_map(|n| n.data.iter())
+                .sum::<usize>()
+                .to_string()),
+            _ => Err(anyhow!(
+                "usage: query node ID | query value ID | query metadata"
+            )),
+        }
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::Session;
+    let s = Day08::new(EXAMPLE)?;
+    assert_eq!(s.query(&["metadata"])?, "138");
+    assert_eq!(s.query(&["value", "0"])?, "66");
+    assert_eq!(s.query(&["value", "2"])?, "0");
+    assert_eq!(s.query(&["node", "2"])?, "children [3]\nmetadata [2]\n");
+    assert_eq!(
+        s.show(&[])?,
+        "0 [1, 1, 2] value 66\n  1 [10, 11, 12] value 33\n  2 [2] value 0\n    3 [99] value 99\n"
+    );
+    assert!(s.query(&["node", "4"]).is_err());
+    Ok(())
+}
@@ -0,0 +1,75 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+
+use day09::*;
+
+pub struct Day09 {
+    state: State,
+}
+
+impl Day09 {
+    pub fn new(input: &str) -> Result<Day09> {
+        let (players, lastmarble) = parser::parse(input.as_bytes())?;
+        Ok(Day09 {
+            state: State::new(players, lastmarble),
+        })
+    }
+}
+
+impl super::Session for Day09 {
+    fn help(&self) -> &'static str {
+        "day09:
+  step [N]        place the next N marbles
+  show            turn, current player and the circle
+  query score     highest score so far
+  query scores    score of each player
+"
+    }
+
+    fn step(&mut self, n: usize) -> Result<String> {
+        for _ in 0..n {
+            if self.state.turn > self.state.lastmarble {
+                return Err(anyhow!("game over at turn {}", self.state.turn));
+            }
+            self.state.play();
+        }
+        Ok(format!("turn {}", self.state.turn))
+    }
+
+    fn show(&self, args: &[&str]) -> Result<String> {
+        if !args.is_empty() {
+            return Err(anyhow!("usage: show"));
+        }
+        Ok(self.state.to_string())
+    }
+
+    fn query(&self, args: &[&str]) -> Result<String> {
+        match args {
+            ["score"] => Ok(self.state.max_score().to_string()),
+            ["scores"] => Ok(self
+                .state
+                .scores
+                .iter()
+                .enumerate()
+                .map(|(p, s)| format!("player {}: {}\n", p + 1, s))
+                .collect()),
+            _ => Err(anyhow!("usage: query score | query scores")),
+        }
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::Session;
+    let mut s = Day09::new(EXAMPLE)?;
+    assert_eq!(s.step(3)?, "turn 3");
+    assert_eq!(s.show(&[])?, "  3 /  25 [2]  0   2   1  (3) ");
+    assert_eq!(s.step(23)?, "turn 26");
+    assert!(s.step(1).is_err());
+    assert_eq!(s.query(&["score"])?, "32");
+    Ok(())
+}
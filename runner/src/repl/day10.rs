@@ -0,0 +1,89 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+
+use day10::*;
+
+/// Largest area that `show` renders
+const SHOW_MAX_AREA: i64 = 200 * 100;
+
+pub struct Day10 {
+    posvel: Posvel,
+    seconds: usize,
+}
+
+impl Day10 {
+    pub fn new(input: &str) -> Result<Day10> {
+        Ok(Day10 {
+            posvel: parser::parse(input.as_bytes())?,
+            seconds: 0,
+        })
+    }
+
+    fn bbox(&self) -> Result<(Pos, Pos)> {
+        self.posvel.bbox().ok_or_else(|| anyhow!("no points"))
+    }
+}
+
+impl super::Session for Day10 {
+    fn help(&self) -> &'static str {
+        "day10:
+  step [N]            move the points N seconds forward
+  show                render the points, if they are close enough
+  query bbox          bounding box of the points
+  query verticality   number of points right above another one
+  query seconds       seconds elapsed
+"
+    }
+
+    fn step(&mut self, n: usize) -> Result<String> {
+        for _ in 0..n {
+            self.posvel.step()?;
+        }
+        self.seconds += n;
+        Ok(format!("{} seconds", self.seconds))
+    }
+
+    fn show(&self, args: &[&str]) -> Result<String> {
+        if !args.is_empty() {
+            return Err(anyhow!("usage: show"));
+        }
+        let (tl, br) = self.bbox()?;
+        let area = (br.0 - tl.0 + 1) as i64 * (br.1 - tl.1 + 1) as i64;
+        if area > SHOW_MAX_AREA {
+            return Err(anyhow!(
+                "points are spread over {} cells, step closer",
+                area
+            ));
+        }
+        Ok(self.posvel.to_string())
+    }
+
+    fn query(&self, args: &[&str]) -> Result<String> {
+        match args {
+            ["bbox"] => {
+                let (tl, br) = self.bbox()?;
+                Ok(format!("({}, {}) - ({}, {})", tl.0, tl.1, br.0, br.1))
+            }
+            ["verticality"] => Ok(self.posvel.verticality().to_string()),
+            ["seconds"] => Ok(self.seconds.to_string()),
+            _ => Err(anyhow!(
+                "usage: query bbox | query verticality | query seconds"
+            )),
+        }
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::Session;
+    let mut s = Day10::new(EXAMPLE)?;
+    assert_eq!(s.query(&["bbox"])?, "(-6, -4) - (15, 11)");
+    assert_eq!(s.step(3)?, "3 seconds");
+    assert_eq!(s.query(&["bbox"])?, "(0, 0) - (9, 7)");
+    assert!(s.show(&[])?.starts_with("#   #  ###\n"));
+    Ok(())
+}
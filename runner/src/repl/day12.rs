@@ -0,0 +1,79 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+use day12::*;
+
+pub struct Day12 {
+    state: State,
+    rules: BTreeMap<Match, bool>,
+    generation: usize,
+}
+
+impl Day12 {
+    pub fn new(input: &str) -> Result<Day12> {
+        let (state, rules) = parser::parse(input.as_bytes())?;
+        Ok(Day12 {
+            state,
+            rules: rules.into_iter().collect(),
+            generation: 0,
+        })
+    }
+}
+
+impl super::Session for Day12 {
+    fn help(&self) -> &'static str {
+        "day12:
+  step [N]           advance N generations
+  show               pots with plants, from the leftmost to the rightmost
+  query sum          sum of the numbers of the pots with plants
+  query count        number of pots with plants
+  query generation   current generation
+"
+    }
+
+    fn step(&mut self, n: usize) -> Result<String> {
+        for _ in 0..n {
+            self.state = nextgen(std::mem::take(&mut self.state), &self.rules);
+        }
+        self.generation += n;
+        Ok(format!("generation {}", self.generation))
+    }
+
+    fn show(&self, args: &[&str]) -> Result<String> {
+        if !args.is_empty() {
+            return Err(anyhow!("usage: show"));
+        }
+        let (Some(&first), Some(&last)) = (self.state.first(), self.state.last()) else {
+            return Ok("no plants".to_string());
+        };
+        let pots = (first..=last)
+            .map(|i| if self.state.contains(&i) { '#' } else { '.' })
+            .collect::<String>();
+        Ok(format!("{:>5}: {}", first, pots))
+    }
+
+    fn query(&self, args: &[&str]) -> Result<String> {
+        match args {
+            ["sum"] => Ok(self.state.iter().sum::<i64>().to_string()),
+            ["count"] => Ok(self.state.len().to_string()),
+            ["generation"] => Ok(self.generation.to_string()),
+            _ => Err(anyhow!("usage: query sum | query count | query generation")),
+        }
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::Session;
+    let mut s = Day12::new(EXAMPLE)?;
+    assert_eq!(s.show(&[])?, "    0: #..#.#..##......###...###");
+    assert_eq!(s.step(20)?, "generation 20");
+    assert_eq!(s.query(&["sum"])?, "325");
+    assert_eq!(s.show(&[])?, "   -2: #....##....#####...#######....#.#..##");
+    Ok(())
+}
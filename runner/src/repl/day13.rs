@@ -0,0 +1,97 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+
+use day13::*;
+
+pub struct Day13 {
+    grid: Grid,
+    carts: Vec<Cart>,
+    ticks: usize,
+}
+
+impl Day13 {
+    pub fn new(input: &str) -> Result<Day13> {
+        let (grid, carts) = parser::parse(input.as_bytes())?;
+        Ok(Day13 {
+            grid,
+            carts,
+            ticks: 0,
+        })
+    }
+}
+
+/// Renders the tracks and carts without the empty area of the grid
+fn render(grid: &Grid, carts: &[Cart]) -> String {
+    let g = gridcarts(grid, carts);
+    let mut lines = vec![String::new(); Qa::HEIGHT as usize];
+    for qa in Qa::iter() {
+        lines[qa.tuple().1 as usize].push(g[qa]);
+    }
+    while lines.last().map(|l| l.trim().is_empty()) == Some(true) {
+        lines.pop();
+    }
+    lines
+        .into_iter()
+        .map(|l| format!("{}\n", l.trim_end()))
+        .collect()
+}
+
+impl super::Session for Day13 {
+    fn help(&self) -> &'static str {
+        "day13:
+  step [N]        move the carts N ticks, removing the ones that crash
+  show            tracks with the carts
+  query carts     position and direction of each cart
+  query ticks     ticks elapsed
+"
+    }
+
+    fn step(&mut self, n: usize) -> Result<String> {
+        let mut out = String::new();
+        for _ in 0..n {
+            if self.carts.len() < 2 {
+                out.push_str("no carts left to crash\n");
+                break;
+            }
+            self.ticks += 1;
+            for qa in tick(&self.grid, &mut self.carts)? {
+                out.push_str(&format!("tick {}: crash at {:?}\n", self.ticks, qa.tuple()));
+            }
+        }
+        out.push_str(&format!("tick {}, {} carts", self.ticks, self.carts.len()));
+        Ok(out)
+    }
+
+    fn show(&self, args: &[&str]) -> Result<String> {
+        if !args.is_empty() {
+            return Err(anyhow!("usage: show"));
+        }
+        Ok(render(&self.grid, &self.carts))
+    }
+
+    fn query(&self, args: &[&str]) -> Result<String> {
+        match args {
+            ["carts"] => Ok(self
+                .carts
+                .iter()
+                .map(|c| format!("{:?} {:?}\n", c.qa.tuple(), c.qr))
+                .collect()),
+            ["ticks"] => Ok(self.ticks.to_string()),
+            _ => Err(anyhow!("usage: query carts | query ticks")),
+        }
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::Session;
+    let mut s = Day13::new(EXAMPLE)?;
+    assert_eq!(s.show(&[])?.lines().next(), Some("/->-\\"));
+    assert_eq!(s.query(&["carts"])?, "(2, 0) E\n(9, 3) S\n");
+    assert_eq!(s.step(14)?, "tick 14: crash at (7, 3)\ntick 14, 0 carts");
+    Ok(())
+}
@@ -0,0 +1,115 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+use serde_json::Value;
+use std::str::FromStr;
+
+use super::Day;
+
+mod day01;
+mod day03;
+mod day04;
+mod day06;
+
+/// Output format of a report
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Dot,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Format> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "dot" => Ok(Format::Dot),
+            "csv" => Ok(Format::Csv),
+            _ => Err(anyhow!(
+                "invalid format {:?}, expected text, json, dot or csv",
+                s
+            )),
+        }
+    }
+}
+
+/// Day-specific knobs of the reports
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Passes through the input considered by day01
+    pub passes: usize,
+    /// Time of day in which day04 observes the guards
+    pub window: ::day04::Window,
+    /// Cells shown in the day06 map, the bounding box of the centers
+    /// if None
+    pub viewport: Option<::day06::Viewport>,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            passes: 10,
+            window: ::day04::Window::default(),
+            viewport: None,
+        }
+    }
+}
+
+/// Analysis of an input beyond the puzzle answers
+pub trait Report {
+    fn text(&self) -> String;
+
+    fn json(&self) -> Value;
+
+    /// Graphviz representation, for the reports that are graphs
+    fn dot(&self) -> Result<String> {
+        Err(anyhow!("this report has no dot output"))
+    }
+
+    /// Table representation, for the reports that are matrices
+    fn csv(&self) -> Result<String> {
+        Err(anyhow!("this report has no csv output"))
+    }
+
+    fn render(&self, format: Format) -> Result<String> {
+        match format {
+            Format::Text => Ok(self.text()),
+            Format::Json => Ok(format!("{:#}\n", self.json())),
+            Format::Dot => self.dot(),
+            Format::Csv => self.csv(),
+        }
+    }
+}
+
+pub fn report(day: Day, input: &str, options: &Options) -> Result<Box<dyn Report>> {
+    Ok(match day.0 {
+        1 => Box::new(day01::Day01::new(input, options)?),
+        3 => Box::new(day03::Day03::new(input)?),
+        4 => Box::new(day04::Day04::new(input, &options.window)?),
+        6 => Box::new(day06::Day06::new(input, options.viewport)?),
+        _ => return Err(anyhow!("{} has no report", day)),
+    })
+}
+
+pub fn run(day: Day, input: &str, options: &Options, format: Format) -> Result<()> {
+    print!("{}", report(day, input, options)?.render(format)?);
+    Ok(())
+}
+
+#[test]
+fn test() -> Result<()> {
+    assert_eq!("json".parse::<Format>()?, Format::Json);
+    assert!("xml".parse::<Format>().is_err());
+    let day01 = report(Day(1), ::day01::EXAMPLE, &Options::default())?;
+    assert!(day01.render(Format::Dot).is_err());
+    assert!(day01.render(Format::Csv).is_err());
+    assert!(report(Day(14), "", &Options::default()).is_err());
+    Ok(())
+}
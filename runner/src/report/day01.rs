@@ -0,0 +1,77 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::Result;
+use serde_json::json;
+use serde_json::Value;
+
+use day01::*;
+
+use super::Options;
+
+pub struct Day01(DriftReport);
+
+impl Day01 {
+    pub fn new(input: &str, options: &Options) -> Result<Day01> {
+        let deltas = parse(input.as_bytes())?;
+        Ok(Day01(DriftReport::new(&deltas, options.passes)))
+    }
+}
+
+fn repeat(frequency: i64, pass: usize, index: usize) -> Value {
+    json!({ "frequency": frequency, "pass": pass, "index": index })
+}
+
+impl super::Report for Day01 {
+    fn text(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn json(&self) -> Value {
+        let r = &self.0;
+        let first_repeat = match r.first_repeat {
+            FirstRepeat::Found {
+                frequency,
+                pass,
+                index,
+            } => repeat(frequency, pass, index),
+            FirstRepeat::Never => Value::Null,
+        };
+        json!({
+            "deltas": r.deltas,
+            "min": r.range.map(|r| r.0),
+            "max": r.range.map(|r| r.1),
+            "drift": r.drift,
+            "first_repeat": first_repeat,
+            "passes_to_repeat": r.passes_to_repeat(),
+            "passes": r.passes,
+            "repeats": r
+                .repeats
+                .iter()
+                .map(|r| repeat(r.frequency, r.pass, r.index))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::{Format, Report};
+    let r = Day01::new(
+        EXAMPLE,
+        &Options {
+            passes: 2,
+            ..Options::default()
+        },
+    )?;
+    let j = r.json();
+    assert_eq!(j["drift"], 3);
+    assert_eq!(j["first_repeat"], repeat(2, 1, 1));
+    assert_eq!(j["passes_to_repeat"], 2);
+    assert_eq!(j["repeats"].as_array().map(Vec::len), Some(1));
+    assert!(r.render(Format::Text)?.contains("drift per pass: +3\n"));
+    let never = Day01::new("+1\n", &Options::default())?.json();
+    assert_eq!(never["first_repeat"], Value::Null);
+    Ok(())
+}
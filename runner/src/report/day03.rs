@@ -0,0 +1,73 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::Result;
+use serde_json::json;
+use serde_json::Value;
+
+use day03::*;
+
+/// The conflict graph of the claims
+pub struct Day03(ConflictGraph);
+
+impl Day03 {
+    pub fn new(input: &str) -> Result<Day03> {
+        let claims = parser::parse(input.as_bytes())?;
+        Ok(Day03(ConflictGraph::new(claims)))
+    }
+}
+
+impl super::Report for Day03 {
+    fn text(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn json(&self) -> Value {
+        let g = &self.0;
+        let claims = g
+            .claims
+            .iter()
+            .zip(&g.overlaps)
+            .map(|(c, overlaps)| {
+                let overlaps = overlaps
+                    .iter()
+                    .map(|&(j, area)| json!({ "id": g.claims[j].id, "area": area }))
+                    .collect::<Vec<_>>();
+                json!({ "id": c.id, "overlaps": overlaps })
+            })
+            .collect::<Vec<_>>();
+        let components = g
+            .components()
+            .into_iter()
+            .map(|component| {
+                component
+                    .into_iter()
+                    .map(|i| g.claims[i].id)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let isolated = g.isolated().iter().map(|c| c.id).collect::<Vec<_>>();
+        json!({
+            "claims": claims,
+            "components": components,
+            "isolated": isolated,
+        })
+    }
+
+    fn dot(&self) -> Result<String> {
+        Ok(self.0.dot())
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::{Format, Report};
+    let r = Day03::new(EXAMPLE)?;
+    let j = r.json();
+    assert_eq!(j["claims"][0]["overlaps"], json!([{ "id": 2, "area": 4 }]));
+    assert_eq!(j["components"], json!([[1, 2]]));
+    assert_eq!(j["isolated"], json!([3]));
+    assert!(r.render(Format::Dot)?.starts_with("graph claims {\n"));
+    Ok(())
+}
@@ -0,0 +1,155 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::Result;
+use serde_json::json;
+use serde_json::Value;
+
+use day04::*;
+
+/// Sleep statistics of the guards and the choices of each strategy
+pub struct Day04 {
+    log: ShiftLog,
+    stats: SleepStats,
+}
+
+impl Day04 {
+    pub fn new(input: &str, window: &Window) -> Result<Day04> {
+        let log = ShiftLog::new(parser::parse(input.as_bytes())?)?;
+        let stats = SleepStats::new(&log, window);
+        Ok(Day04 { log, stats })
+    }
+}
+
+fn minutes(most_slept: &MostSlept) -> String {
+    most_slept
+        .minutes
+        .iter()
+        .map(|m| m.format("%H:%M").to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl super::Report for Day04 {
+    fn text(&self) -> String {
+        let mut text = String::new();
+        for g in &self.stats.guards {
+            text.push_str(&format!(
+                "#{}: {} minutes asleep in {} shifts",
+                g.guard,
+                g.total(),
+                g.shifts
+            ));
+            if let Some(most_slept) = g.most_slept() {
+                text.push_str(&format!(
+                    ", {} times on {}",
+                    most_slept.count,
+                    minutes(&most_slept)
+                ));
+            }
+            text.push('\n');
+        }
+        for strategy in Strategy::ALL {
+            match strategy.choose(&self.stats) {
+                Some(choice) => text.push_str(&format!(
+                    "{}: #{} at {}{}\n",
+                    strategy,
+                    choice.guard,
+                    choice.most_slept.minute().format("%H:%M"),
+                    if choice.most_slept.is_tie() {
+                        " (tie)"
+                    } else {
+                        ""
+                    }
+                )),
+                None => text.push_str(&format!("{}: no guard sleeps\n", strategy)),
+            }
+        }
+        text.push_str(&self.log.chart(&self.stats.window));
+        text
+    }
+
+    fn json(&self) -> Value {
+        let guards = self
+            .stats
+            .guards
+            .iter()
+            .map(|g| {
+                json!({
+                    "guard": g.guard,
+                    "shifts": g.shifts,
+                    "total": g.total(),
+                    "most_slept": g.most_slept().map(|m| json!({
+                        "count": m.count,
+                        "minutes": minutes(&m).split(' ').collect::<Vec<_>>(),
+                    })),
+                    "histogram": g.histogram,
+                })
+            })
+            .collect::<Vec<_>>();
+        let strategies = Strategy::ALL
+            .iter()
+            .map(|strategy| {
+                let choice = strategy.choose(&self.stats);
+                json!({
+                    "strategy": strategy.to_string(),
+                    "guard": choice.as_ref().map(|c| c.guard),
+                    "minute": choice
+                        .as_ref()
+                        .map(|c| c.most_slept.minute().format("%H:%M").to_string()),
+                    "tie": choice.as_ref().map(|c| c.most_slept.is_tie()),
+                })
+            })
+            .collect::<Vec<_>>();
+        let timelines = self
+            .log
+            .timelines(&self.stats.window)
+            .iter()
+            .map(|t| {
+                let asleep = t
+                    .asleep
+                    .iter()
+                    .map(|&a| if a { '#' } else { '.' })
+                    .collect::<String>();
+                json!({
+                    "date": t.date.to_string(),
+                    "guard": t.guard,
+                    "asleep": asleep,
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "window": self.stats.window.to_string(),
+            "guards": guards,
+            "strategies": strategies,
+            "timelines": timelines,
+        })
+    }
+
+    fn csv(&self) -> Result<String> {
+        Ok(self.stats.csv())
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::{Format, Report};
+    let r = Day04::new(EXAMPLE, &Window::default())?;
+    let text = r.text();
+    assert!(text.starts_with("#10: 50 minutes asleep in 2 shifts, 2 times on 00:24\n"));
+    assert!(text.contains("most asleep: #10 at 00:24\n"));
+    assert!(text.contains("most frequent minute: #99 at 00:45\n"));
+    assert!(text.contains("11-03  #10  "));
+    let j = r.json();
+    assert_eq!(j["guards"][1]["total"], json!(30));
+    assert_eq!(j["strategies"][1]["guard"], json!(99));
+    assert_eq!(j["timelines"][0]["date"], json!("1518-11-01"));
+    assert!(r.render(Format::Csv)?.starts_with("guard,00:00,00:01,"));
+    assert_eq!(j["strategies"][1]["minute"], json!("00:45"));
+    assert!(r.render(Format::Dot).is_err());
+    let r = Day04::new(EXAMPLE, &"23:00-01:00".parse()?)?;
+    assert!(r.text().contains("Date   ID   Time\n"));
+    assert_eq!(r.json()["window"], json!("23:00-01:00"));
+    Ok(())
+}
@@ -0,0 +1,76 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::Result;
+use serde_json::json;
+use serde_json::Value;
+
+use day06::*;
+
+/// Area of the region of each center and the map of the regions
+pub struct Day06 {
+    regions: Regions,
+    map: String,
+}
+
+impl Day06 {
+    pub fn new(input: &str, viewport: Option<Viewport>) -> Result<Day06> {
+        let regions = Regions::new(Metric::Manhattan, parser::parse(input.as_bytes())?)?;
+        let map = regions.map(viewport)?;
+        Ok(Day06 { regions, map })
+    }
+}
+
+impl super::Report for Day06 {
+    fn text(&self) -> String {
+        format!("{}{}", self.regions, self.map)
+    }
+
+    fn json(&self) -> Value {
+        let r = &self.regions;
+        let centers = r
+            .centers
+            .iter()
+            .zip(&r.areas)
+            .enumerate()
+            .map(|(i, (c, area))| {
+                json!({
+                    "index": i,
+                    "label": label(i, true).to_string(),
+                    "x": c.0,
+                    "y": c.1,
+                    "area": area,
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "metric": r.metric.to_string(),
+            "centers": centers,
+            "largest_finite_area": r.areas.iter().flatten().max(),
+            "ties": r.ties.iter().map(|t| json!([t.0, t.1])).collect::<Vec<_>>(),
+        })
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::{Format, Report};
+    let r = Day06::new(EXAMPLE, None)?;
+    let j = r.json();
+    assert_eq!(j["centers"][0]["label"], "A");
+    assert_eq!(j["centers"][5]["index"], 5);
+    assert_eq!(j["centers"][0]["area"], Value::Null);
+    assert_eq!(j["centers"][4]["area"], 17);
+    assert_eq!(j["largest_finite_area"], 17);
+    assert_eq!(j["ties"].as_array().map(Vec::len), Some(8));
+    let text = r.render(Format::Text)?;
+    assert!(text.contains("D (3, 4): 9\n"));
+    assert!(text.contains("\nAaaa.ccc\n"));
+    let zoomed = Day06::new(EXAMPLE, Some("3,4:5,5".parse()?))?;
+    assert!(zoomed
+        .text()
+        .ends_with("infinite\nties in the bounding box: 8\nDde\ndeE\n"));
+    assert!(Day06::new(EXAMPLE, Some("0,0:2000,2000".parse()?)).is_err());
+    Ok(())
+}
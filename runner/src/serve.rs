@@ -0,0 +1,217 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+use serde_json::json;
+use serde_json::Value;
+use std::thread;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use super::solvers;
+use super::Day;
+
+mod frames;
+
+pub use frames::FrameReader;
+
+const INDEX: &str = include_str!("serve/index.html");
+
+/// Frames streamed by default, and the maximum allowed
+const FRAMES_DEFAULT: usize = 200;
+const FRAMES_MAX: usize = 100_000;
+
+pub enum Reply {
+    Html(&'static str),
+    Text(String),
+    Json(u16, Value),
+    Frames(FrameReader),
+}
+
+fn error(status: u16, e: impl std::fmt::Display) -> Reply {
+    Reply::Json(status, json!({ "error": e.to_string() }))
+}
+
+fn days() -> Value {
+    let mut days = solvers::SOLVERS.iter().map(|s| s.day).collect::<Vec<_>>();
+    days.dedup();
+    let days = days
+        .into_iter()
+        .map(|day| {
+            let parts = solvers::SOLVERS
+                .iter()
+                .filter(|s| s.day == day)
+                .map(|s| s.part.to_string())
+                .collect::<Vec<_>>();
+            json!({
+                "day": day.to_string(),
+                "parts": parts,
+                "example": day.example().is_some(),
+                "simulation": frames::SIMULATIONS.contains(&day),
+            })
+        })
+        .collect::<Vec<_>>();
+    json!(days)
+}
+
+fn param(query: &str, name: &str, default: usize) -> Result<usize> {
+    for kv in query.split('&') {
+        if let Some((k, v)) = kv.split_once('=') {
+            if k == name {
+                return v
+                    .parse()
+                    .map_err(|_| anyhow!("invalid value for {}: {:?}", name, v));
+            }
+        }
+    }
+    Ok(default)
+}
+
+fn frames(day: &str, query: &str, input: &str) -> Result<Reply> {
+    let day = day.parse::<Day>()?;
+    let count = param(query, "count", FRAMES_DEFAULT)?;
+    if count > FRAMES_MAX {
+        return Err(anyhow!("count is limited to {}", FRAMES_MAX));
+    }
+    let every = param(query, "every", 1)?;
+    let sim = frames::simulation(day, input)?;
+    Ok(Reply::Frames(FrameReader::new(sim, count, every)))
+}
+
+/// Maps a request to its reply
+pub fn route(method: &Method, url: &str, body: &str) -> Reply {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    match (method, segments.as_slice()) {
+        (Method::Get, [""]) => Reply::Html(INDEX),
+        (Method::Get, ["api", "days"]) => Reply::Json(200, days()),
+        (Method::Get, ["api", "example", day]) => {
+            match day.parse::<Day>().ok().and_then(|d| d.example()) {
+                Some(example) => Reply::Text(example.to_string()),
+                None => error(404, format!("no example for {}", day)),
+            }
+        }
+        (Method::Post, ["api", "solve", day, part]) => {
+            let solved = day.parse::<Day>().and_then(|day| {
+                let part = part
+                    .parse::<char>()
+                    .map_err(|_| anyhow!("invalid part {:?}", part))?;
+                solvers::solve(day, part, body)
+            });
+            match solved {
+                Ok(answer) => Reply::Json(200, json!({ "answer": answer })),
+                Err(e) => error(400, e),
+            }
+        }
+        (Method::Post, ["api", "frames", day]) => {
+            frames(day, query, body).unwrap_or_else(|e| error(400, e))
+        }
+        _ => error(404, "not found"),
+    }
+}
+
+fn header(value: &str) -> Header {
+    Header::from_bytes("Content-Type", value).unwrap()
+}
+
+fn handle(mut request: Request) -> Result<()> {
+    let mut body = String::new();
+    request.as_reader().read_to_string(&mut body)?;
+    match route(request.method(), request.url(), &body) {
+        Reply::Html(html) => {
+            request.respond(Response::from_string(html).with_header(header("text/html")))?
+        }
+        Reply::Text(text) => {
+            request.respond(Response::from_string(text).with_header(header("text/plain")))?
+        }
+        Reply::Json(status, value) => request.respond(
+            Response::from_string(value.to_string())
+                .with_status_code(status)
+                .with_header(header("application/json")),
+        )?,
+        Reply::Frames(reader) => request.respond(Response::new(
+            200.into(),
+            vec![header("application/x-ndjson")],
+            reader,
+            None,
+            None,
+        ))?,
+    }
+    Ok(())
+}
+
+/// Serves each request in its own thread, forever
+pub fn serve(server: Server) {
+    for request in server.incoming_requests() {
+        thread::spawn(move || {
+            if let Err(e) = handle(request) {
+                eprintln!("error: {}", e);
+            }
+        });
+    }
+}
+
+pub fn run(port: u16) -> Result<()> {
+    let server = Server::http(("127.0.0.1", port)).map_err(|e| anyhow!("{}", e))?;
+    eprintln!("serving on http://{}/", server.server_addr());
+    serve(server);
+    Ok(())
+}
+
+#[test]
+fn test_route() -> Result<()> {
+    let Reply::Json(200, days) = route(&Method::Get, "/api/days", "") else {
+        panic!("invalid reply");
+    };
+    assert_eq!(days[8]["day"], "day09");
+    assert_eq!(days[8]["parts"], json!(["a", "b"]));
+    assert_eq!(days[8]["simulation"], true);
+    let Reply::Json(200, answer) = route(&Method::Post, "/api/solve/day08/b", day08::EXAMPLE)
+    else {
+        panic!("invalid reply");
+    };
+    assert_eq!(answer["answer"], "66");
+    let Reply::Json(400, e) = route(&Method::Post, "/api/solve/8/c", day08::EXAMPLE) else {
+        panic!("invalid reply");
+    };
+    assert_eq!(e["error"], "no solver for day08c");
+    assert!(matches!(
+        route(&Method::Get, "/api/example/day13", ""),
+        Reply::Text(_)
+    ));
+    assert!(matches!(
+        route(&Method::Post, "/api/frames/day13?count=x", day13::EXAMPLE),
+        Reply::Json(400, _)
+    ));
+    assert!(matches!(
+        route(&Method::Get, "/api/nothing", ""),
+        Reply::Json(404, _)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_serve() -> Result<()> {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    let server = Server::http("127.0.0.1:0").map_err(|e| anyhow!("{}", e))?;
+    let addr = server.server_addr().to_ip().unwrap();
+    thread::spawn(move || serve(server));
+    let mut stream = TcpStream::connect(addr)?;
+    let body = day12::EXAMPLE;
+    write!(
+        stream,
+        "POST /api/frames/day12?count=21 HTTP/1.0\r\nContent-Length: {}\r\n\r\n{}",
+        body.len(),
+        body
+    )?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    assert!(response.starts_with("HTTP/1.0 200"), "{}", response);
+    let last = response.lines().last().unwrap();
+    let frame = serde_json::from_str::<Value>(last)?;
+    assert_eq!(frame["generation"], 20);
+    assert_eq!(frame["sum"], 325);
+    Ok(())
+}
@@ -0,0 +1,317 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+use serde_json::json;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io;
+
+use crate::Day;
+
+/// A simulation that produces JSON frames for the browser
+pub trait Simulation: Send {
+    fn frame(&self) -> Value;
+
+    /// Advances one tick, returns false when the simulation is over
+    fn step(&mut self) -> Result<bool>;
+}
+
+pub fn simulation(day: Day, input: &str) -> Result<Box<dyn Simulation>> {
+    Ok(match day.0 {
+        9 => Box::new(Day09::new(input)?),
+        10 => Box::new(Day10::new(input)?),
+        12 => Box::new(Day12::new(input)?),
+        13 => Box::new(Day13::new(input)?),
+        _ => return Err(anyhow!("{} has no simulation", day)),
+    })
+}
+
+pub const SIMULATIONS: &[Day] = &[Day(9), Day(10), Day(12), Day(13)];
+
+struct Day09 {
+    state: day09::State,
+}
+
+impl Day09 {
+    fn new(input: &str) -> Result<Day09> {
+        let (players, lastmarble) = day09::parser::parse(input.as_bytes())?;
+        Ok(Day09 {
+            state: day09::State::new(players, lastmarble),
+        })
+    }
+}
+
+impl Simulation for Day09 {
+    fn frame(&self) -> Value {
+        let mut circle = vec![0];
+        let mut m = self.state.mlinks[0].1;
+        while m != 0 {
+            circle.push(m);
+            m = self.state.mlinks[m].1;
+        }
+        json!({
+            "turn": self.state.turn,
+            "player": self.state.nextplayer,
+            "current": self.state.mcurr,
+            "circle": circle,
+            "score": self.state.max_score(),
+        })
+    }
+
+    fn step(&mut self) -> Result<bool> {
+        if self.state.turn > self.state.lastmarble {
+            return Ok(false);
+        }
+        self.state.play();
+        Ok(true)
+    }
+}
+
+struct Day10 {
+    posvel: day10::Posvel,
+    second: usize,
+}
+
+impl Day10 {
+    fn new(input: &str) -> Result<Day10> {
+        Ok(Day10 {
+            posvel: day10::parser::parse(input.as_bytes())?,
+            second: 0,
+        })
+    }
+}
+
+impl Simulation for Day10 {
+    fn frame(&self) -> Value {
+        let points = self
+            .posvel
+            .positions()
+            .map(|p| [p.0, p.1])
+            .collect::<Vec<_>>();
+        json!({
+            "second": self.second,
+            "points": points,
+        })
+    }
+
+    fn step(&mut self) -> Result<bool> {
+        if !self.posvel.valid() {
+            return Ok(false);
+        }
+        self.posvel.step()?;
+        self.second += 1;
+        Ok(true)
+    }
+}
+
+struct Day12 {
+    state: day12::State,
+    rules: BTreeMap<day12::Match, bool>,
+    generation: usize,
+}
+
+impl Day12 {
+    fn new(input: &str) -> Result<Day12> {
+        let (state, rules) = day12::parser::parse(input.as_bytes())?;
+        Ok(Day12 {
+            state,
+            rules: rules.into_iter().collect(),
+            generation: 0,
+        })
+    }
+}
+
+impl Simulation for Day12 {
+    fn frame(&self) -> Value {
+        let first = self.state.first().copied().unwrap_or(0);
+        let last = self.state.last().copied().unwrap_or(-1);
+        let pots = (first..=last)
+            .map(|i| if self.state.contains(&i) { '#' } else { '.' })
+            .collect::<String>();
+        json!({
+            "generation": self.generation,
+            "first": first,
+            "pots": pots,
+            "sum": self.state.iter().sum::<i64>(),
+        })
+    }
+
+    fn step(&mut self) -> Result<bool> {
+        if self.state.is_empty() {
+            return Ok(false);
+        }
+        self.state = day12::nextgen(std::mem::take(&mut self.state), &self.rules);
+        self.generation += 1;
+        Ok(true)
+    }
+}
+
+struct Day13 {
+    grid: day13::Grid,
+    carts: Vec<day13::Cart>,
+    tick: usize,
+    crashes: Vec<[u16; 2]>,
+}
+
+impl Day13 {
+    fn new(input: &str) -> Result<Day13> {
+        let (grid, carts) = day13::parser::parse(input.as_bytes())?;
+        Ok(Day13 {
+            grid,
+            carts,
+            tick: 0,
+            crashes: vec![],
+        })
+    }
+
+    fn tracks(&self) -> Vec<String> {
+        let mut lines = vec![String::new(); day13::Qa::HEIGHT as usize];
+        for qa in day13::Qa::iter() {
+            lines[qa.tuple().1 as usize].push(char::from(&self.grid[qa]));
+        }
+        while lines.last().map(|l| l.trim().is_empty()) == Some(true) {
+            lines.pop();
+        }
+        lines
+            .into_iter()
+            .map(|l| l.trim_end().to_string())
+            .collect()
+    }
+}
+
+impl Simulation for Day13 {
+    fn frame(&self) -> Value {
+        let carts = self
+            .carts
+            .iter()
+            .map(|c| {
+                let t = c.qa.tuple();
+                json!({"x": t.0, "y": t.1, "dir": format!("{:?}", c.qr)})
+            })
+            .collect::<Vec<_>>();
+        let mut frame = json!({
+            "tick": self.tick,
+            "carts": carts,
+            "crashes": self.crashes,
+        });
+        // The tracks don't change, send them only in the first frame
+        if self.tick == 0 {
+            frame["tracks"] = json!(self.tracks());
+        }
+        frame
+    }
+
+    fn step(&mut self) -> Result<bool> {
+        if self.carts.len() < 2 {
+            return Ok(false);
+        }
+        self.tick += 1;
+        for qa in day13::tick(&self.grid, &mut self.carts)? {
+            let t = qa.tuple();
+            self.crashes.push([t.0, t.1]);
+        }
+        Ok(true)
+    }
+}
+
+/// Streams up to `count` frames as newline-delimited JSON, taking
+/// `every` steps between frames
+pub struct FrameReader {
+    sim: Box<dyn Simulation>,
+    remaining: usize,
+    every: usize,
+    buf: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+impl FrameReader {
+    pub fn new(sim: Box<dyn Simulation>, count: usize, every: usize) -> FrameReader {
+        FrameReader {
+            sim,
+            remaining: count,
+            every: every.max(1),
+            buf: vec![],
+            pos: 0,
+            done: false,
+        }
+    }
+
+    fn next_frame(&mut self) -> Result<()> {
+        let mut line = serde_json::to_vec(&self.sim.frame())?;
+        line.push(b'\n');
+        self.buf = line;
+        self.pos = 0;
+        self.remaining -= 1;
+        for _ in 0..self.every {
+            if !self.sim.step()? {
+                self.done = true;
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl io::Read for FrameReader {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.buf.len() {
+            if self.remaining == 0 || self.done {
+                return Ok(0);
+            }
+            self.next_frame().map_err(io::Error::other)?;
+        }
+        let n = out.len().min(self.buf.len() - self.pos);
+        out[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use std::io::Read;
+    let sim = simulation(Day(9), day09::EXAMPLE)?;
+    let mut out = String::new();
+    FrameReader::new(sim, 3, 2).read_to_string(&mut out)?;
+    let frames = out
+        .lines()
+        .map(serde_json::from_str::<Value>)
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[2]["turn"], 4);
+    assert_eq!(frames[2]["circle"], json!([0, 4, 2, 1, 3]));
+    // Marbles get removed from the circle every 23 turns
+    let sim = simulation(Day(9), day09::EXAMPLE)?;
+    let mut out = String::new();
+    FrameReader::new(sim, 30, 1).read_to_string(&mut out)?;
+    for line in out.lines() {
+        let frame = serde_json::from_str::<Value>(line)?;
+        let circle = frame["circle"].as_array().unwrap();
+        let unique = circle
+            .iter()
+            .map(|m| m.as_u64())
+            .collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(unique.len(), circle.len());
+    }
+    let last = serde_json::from_str::<Value>(out.lines().last().unwrap())?;
+    assert_eq!(
+        last["circle"],
+        json!([
+            0, 16, 8, 17, 4, 18, 19, 2, 24, 20, 25, 10, 26, 21, 5, 22, 11, 1, 12, 6, 13, 3, 14, 7,
+            15
+        ])
+    );
+    // The simulation ends before the count is reached
+    let sim = simulation(Day(13), day13::EXAMPLE)?;
+    let mut out = String::new();
+    FrameReader::new(sim, 100, 1).read_to_string(&mut out)?;
+    let last = serde_json::from_str::<Value>(out.lines().last().unwrap())?;
+    assert_eq!(last["crashes"], json!([[7, 3]]));
+    assert_eq!(out.lines().count(), 15);
+    assert!(simulation(Day(1), "").is_err());
+    Ok(())
+}
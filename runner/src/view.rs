@@ -0,0 +1,270 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+use crossterm::cursor;
+use crossterm::event;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use crossterm::style;
+use crossterm::terminal;
+use crossterm::QueueableCommand;
+use std::io::{stdout, Write};
+use std::time::{Duration, Instant};
+
+use super::Day;
+
+mod day06;
+mod day10;
+mod day11;
+mod day13;
+
+/// Position of a cell in the coordinates of the scene
+pub type Pos = (i64, i64);
+
+/// A simulation that can be displayed by the viewer
+pub trait Scene {
+    /// Advances one tick, returns false when the simulation is over
+    fn step(&mut self) -> Result<bool>;
+
+    /// Non-blank cells of the current frame; when zoomed out, cells
+    /// that come later are drawn over the ones before them
+    fn cells(&self) -> Vec<(Pos, char)>;
+
+    /// Day-specific part of the status bar
+    fn status(&self) -> String;
+}
+
+pub fn scene(day: Day, input: &str) -> Result<Box<dyn Scene>> {
+    Ok(match day.0 {
+        6 => Box::new(day06::Day06::new(input)?),
+        10 => Box::new(day10::Day10::new(input)?),
+        11 => Box::new(day11::Day11::new(input)?),
+        13 => Box::new(day13::Day13::new(input)?),
+        _ => return Err(anyhow!("{} is not supported by the viewer", day)),
+    })
+}
+
+/// Viewport over the scene: each screen cell shows a block of
+/// `zoom` x `zoom` scene cells starting at `offset`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Viewport {
+    pub offset: Pos,
+    pub zoom: i64,
+}
+
+impl Default for Viewport {
+    fn default() -> Viewport {
+        Viewport {
+            offset: (0, 0),
+            zoom: 1,
+        }
+    }
+}
+
+impl Viewport {
+    /// Smallest zoom that shows all cells in a screen of the given size
+    pub fn fit(cells: &[(Pos, char)], width: u16, height: u16) -> Viewport {
+        let xmin = cells.iter().map(|(p, _)| p.0).min().unwrap_or(0);
+        let xmax = cells.iter().map(|(p, _)| p.0).max().unwrap_or(0);
+        let ymin = cells.iter().map(|(p, _)| p.1).min().unwrap_or(0);
+        let ymax = cells.iter().map(|(p, _)| p.1).max().unwrap_or(0);
+        let (width, height) = (i64::from(width.max(1)), i64::from(height.max(1)));
+        let zoomx = (xmax - xmin + width) / width;
+        let zoomy = (ymax - ymin + height) / height;
+        Viewport {
+            offset: (xmin, ymin),
+            zoom: zoomx.max(zoomy).max(1),
+        }
+    }
+
+    /// Downsamples the cells into `height` lines of `width` characters
+    pub fn raster(&self, cells: &[(Pos, char)], width: u16, height: u16) -> Vec<String> {
+        let (width, height) = (usize::from(width), usize::from(height));
+        let mut screen = vec![vec![' '; width]; height];
+        for &((x, y), c) in cells {
+            let sx = (x - self.offset.0).div_euclid(self.zoom);
+            let sy = (y - self.offset.1).div_euclid(self.zoom);
+            if (0..width as i64).contains(&sx) && (0..height as i64).contains(&sy) {
+                screen[sy as usize][sx as usize] = c;
+            }
+        }
+        screen.into_iter().map(String::from_iter).collect()
+    }
+
+    pub fn scroll(&mut self, dx: i64, dy: i64) {
+        self.offset.0 += dx * self.zoom;
+        self.offset.1 += dy * self.zoom;
+    }
+
+    /// Changes the zoom keeping the center of the screen in place
+    pub fn set_zoom(&mut self, zoom: i64, width: u16, height: u16) {
+        let zoom = zoom.max(1);
+        let (hw, hh) = (i64::from(width) / 2, i64::from(height) / 2);
+        self.offset.0 += hw * (self.zoom - zoom);
+        self.offset.1 += hh * (self.zoom - zoom);
+        self.zoom = zoom;
+    }
+}
+
+/// Restores the terminal when dropped, even on errors
+struct Terminal;
+
+impl Terminal {
+    fn new() -> Result<Terminal> {
+        terminal::enable_raw_mode()?;
+        let mut out = stdout();
+        out.queue(terminal::EnterAlternateScreen)?;
+        out.queue(cursor::Hide)?;
+        out.flush()?;
+        Ok(Terminal)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let mut out = stdout();
+        let _ = out.queue(cursor::Show);
+        let _ = out.queue(terminal::LeaveAlternateScreen);
+        let _ = out.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+struct Viewer {
+    day: Day,
+    scene: Box<dyn Scene>,
+    viewport: Viewport,
+    playing: bool,
+    finished: bool,
+    delay: Duration,
+}
+
+impl Viewer {
+    fn step(&mut self) -> Result<()> {
+        if !self.finished && !self.scene.step()? {
+            self.finished = true;
+            self.playing = false;
+        }
+        Ok(())
+    }
+
+    fn draw(&self, width: u16, height: u16) -> Result<()> {
+        let mut out = stdout();
+        let lines = self
+            .viewport
+            .raster(&self.scene.cells(), width, height.saturating_sub(1));
+        for (y, line) in lines.iter().enumerate() {
+            out.queue(cursor::MoveTo(0, y as u16))?;
+            out.queue(style::Print(line))?;
+        }
+        let state = if self.finished {
+            "done"
+        } else if self.playing {
+            "playing"
+        } else {
+            "paused"
+        };
+        let status = format!(
+            " {} | {} | zoom 1:{} | at ({}, {}) | {} | {}ms",
+            self.day,
+            self.scene.status(),
+            self.viewport.zoom,
+            self.viewport.offset.0,
+            self.viewport.offset.1,
+            state,
+            self.delay.as_millis()
+        );
+        let status = format!("{:width$.width$}", status, width = usize::from(width));
+        out.queue(cursor::MoveTo(0, height.saturating_sub(1)))?;
+        out.queue(style::PrintStyledContent(style::Stylize::reverse(status)))?;
+        out.flush()?;
+        Ok(())
+    }
+
+    fn run(&mut self) -> Result<()> {
+        let (width, height) = terminal::size()?;
+        self.viewport = Viewport::fit(&self.scene.cells(), width, height.saturating_sub(1));
+        let mut next = Instant::now();
+        loop {
+            let (width, height) = terminal::size()?;
+            self.draw(width, height)?;
+            let timeout = if self.playing {
+                next.saturating_duration_since(Instant::now())
+            } else {
+                Duration::from_secs(3600)
+            };
+            if !event::poll(timeout)? {
+                if self.playing {
+                    self.step()?;
+                    next = Instant::now() + self.delay;
+                }
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+            let page = (i64::from(width) / 4, i64::from(height) / 4);
+            let zoom = self.viewport.zoom;
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char(' ') => {
+                    self.playing = !self.playing && !self.finished;
+                    next = Instant::now();
+                }
+                KeyCode::Char('n') | KeyCode::Char('.') => self.step()?,
+                KeyCode::Left | KeyCode::Char('h') => self.viewport.scroll(-page.0, 0),
+                KeyCode::Right | KeyCode::Char('l') => self.viewport.scroll(page.0, 0),
+                KeyCode::Up | KeyCode::Char('k') => self.viewport.scroll(0, -page.1),
+                KeyCode::Down | KeyCode::Char('j') => self.viewport.scroll(0, page.1),
+                KeyCode::Char('-') => self.viewport.set_zoom(zoom * 2, width, height),
+                KeyCode::Char('+') | KeyCode::Char('=') => {
+                    self.viewport.set_zoom(zoom / 2, width, height)
+                }
+                KeyCode::Char('0') => {
+                    self.viewport =
+                        Viewport::fit(&self.scene.cells(), width, height.saturating_sub(1))
+                }
+                KeyCode::Char('[') => self.delay = (self.delay * 2).min(Duration::from_secs(5)),
+                KeyCode::Char(']') => self.delay = (self.delay / 2).max(Duration::from_millis(1)),
+                _ => {}
+            }
+        }
+    }
+}
+
+pub fn run(day: Day, input: &str) -> Result<()> {
+    let mut viewer = Viewer {
+        day,
+        scene: scene(day, input)?,
+        viewport: Viewport::default(),
+        playing: false,
+        finished: false,
+        delay: Duration::from_millis(100),
+    };
+    let _terminal = Terminal::new()?;
+    viewer.run()
+}
+
+#[test]
+fn test_viewport() -> Result<()> {
+    let cells = vec![((-4, 0), 'a'), ((0, 0), 'b'), ((5, 3), 'c'), ((4, 2), 'd')];
+    let vp = Viewport::fit(&cells, 5, 2);
+    assert_eq!(
+        vp,
+        Viewport {
+            offset: (-4, 0),
+            zoom: 2
+        }
+    );
+    assert_eq!(vp.raster(&cells, 5, 2), vec!["a b  ", "    d"]);
+    let mut vp = Viewport::default();
+    assert_eq!(vp.raster(&cells, 3, 1), vec!["b  "]);
+    vp.scroll(-2, 0);
+    assert_eq!(vp.raster(&cells, 3, 1), vec!["  b"]);
+    Ok(())
+}
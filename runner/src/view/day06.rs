@@ -0,0 +1,124 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::anyhow;
+use anyhow::Result;
+
+use day06::*;
+
+use super::Pos;
+
+/// Cells around the centers that are also shown
+const MARGIN: i64 = 5;
+
+/// Cells of the largest map, each one is measured against all centers
+const CELLS_MAX: i128 = 1_000_000;
+
+/// Regions growing from the centers, one distance unit per tick
+pub struct Day06 {
+    centers: Vec<Pos>,
+    /// Each cell with its distance to the nearest center and that
+    /// center, or None if there is a tie
+    cells: Vec<(Pos, i128, Option<usize>)>,
+    dist: i128,
+    maxdist: i128,
+}
+
+impl Day06 {
+    pub fn new(input: &str) -> Result<Day06> {
+        let centers = parser::parse(input.as_bytes())?;
+        let (min, max) = bounding_box(&centers).ok_or_else(|| anyhow!("no centers in input"))?;
+        let area =
+            (max.0 - min.0 + 1 + 2 * MARGIN) as i128 * (max.1 - min.1 + 1 + 2 * MARGIN) as i128;
+        if area > CELLS_MAX {
+            return Err(anyhow!(
+                "centers are spread over {} cells, the map is limited to {}",
+                area,
+                CELLS_MAX
+            ));
+        }
+        let mut cells = vec![];
+        for y in min.1 - MARGIN..=max.1 + MARGIN {
+            for x in min.0 - MARGIN..=max.0 + MARGIN {
+                let pos = (x, y);
+                let dist = centers
+                    .iter()
+                    .map(|&c| Metric::Manhattan.distance(c, pos))
+                    .min()
+                    .unwrap_or_default();
+                cells.push((pos, dist, nearest(Metric::Manhattan, &centers, pos)));
+            }
+        }
+        let maxdist = cells.iter().map(|c| c.1).max().unwrap_or(0);
+        Ok(Day06 {
+            centers,
+            cells,
+            dist: 0,
+            maxdist,
+        })
+    }
+}
+
+impl super::Scene for Day06 {
+    fn step(&mut self) -> Result<bool> {
+        if self.dist >= self.maxdist {
+            return Ok(false);
+        }
+        self.dist += 1;
+        Ok(true)
+    }
+
+    fn cells(&self) -> Vec<(Pos, char)> {
+        let regions = self
+            .cells
+            .iter()
+            .filter(|(_, d, _)| 0 < *d && *d <= self.dist)
+            .map(|&(pos, _, owner)| (pos, owner.map(|i| label(i, false)).unwrap_or('.')));
+        let centers = self
+            .centers
+            .iter()
+            .enumerate()
+            .map(|(i, &pos)| (pos, label(i, true)));
+        regions.chain(centers).collect()
+    }
+
+    fn status(&self) -> String {
+        let claimed = self
+            .cells
+            .iter()
+            .filter(|(_, d, _)| *d <= self.dist)
+            .count();
+        format!(
+            "distance {} | claimed {}/{}",
+            self.dist,
+            claimed,
+            self.cells.len()
+        )
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::Scene;
+    let mut s = Day06::new(EXAMPLE)?;
+    assert_eq!(s.cells().len(), 6);
+    assert!(s.step()?);
+    let cells = s.cells();
+    assert!(cells.contains(&((1, 1), 'A')));
+    assert!(cells.contains(&((1, 2), 'a')));
+    assert!(cells.contains(&((5, 4), 'e')));
+    assert!(cells.contains(&((5, 5), 'E')));
+    while s.step()? {}
+    assert_eq!(s.status(), "distance 13 | claimed 342/342");
+    // Tie between B and D
+    assert!(s.cells().contains(&((1, 4), '.')));
+    assert_eq!(
+        Day06::new("0, 0\n20000, 20000\n")
+            .err()
+            .unwrap()
+            .to_string(),
+        "centers are spread over 400440121 cells, the map is limited to 1000000"
+    );
+    Ok(())
+}
@@ -0,0 +1,63 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::Result;
+
+use day10::*;
+
+use super::Pos;
+
+/// The moving points, one second per tick
+pub struct Day10 {
+    posvel: Posvel,
+    seconds: usize,
+}
+
+impl Day10 {
+    pub fn new(input: &str) -> Result<Day10> {
+        Ok(Day10 {
+            posvel: parser::parse(input.as_bytes())?,
+            seconds: 0,
+        })
+    }
+}
+
+impl super::Scene for Day10 {
+    fn step(&mut self) -> Result<bool> {
+        if !self.posvel.valid() {
+            return Ok(false);
+        }
+        self.posvel.step()?;
+        self.seconds += 1;
+        Ok(true)
+    }
+
+    fn cells(&self) -> Vec<(Pos, char)> {
+        self.posvel
+            .positions()
+            .map(|p| ((i64::from(p.0), i64::from(p.1)), '#'))
+            .collect()
+    }
+
+    fn status(&self) -> String {
+        let bbox = self
+            .posvel
+            .bbox()
+            .map(|(tl, br)| format!("({}, {}) - ({}, {})", tl.0, tl.1, br.0, br.1))
+            .unwrap_or_default();
+        format!("second {} | bbox {}", self.seconds, bbox)
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::Scene;
+    let mut s = Day10::new(EXAMPLE)?;
+    for _ in 0..3 {
+        assert!(s.step()?);
+    }
+    assert_eq!(s.status(), "second 3 | bbox (0, 0) - (9, 7)");
+    assert!(s.cells().contains(&((0, 0), '#')));
+    Ok(())
+}
@@ -0,0 +1,109 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::Result;
+
+use day11::*;
+
+use super::Pos;
+
+const SIZE: usize = 300;
+
+/// Shades for the power levels, from -5 to 4
+const SHADES: &[char] = &[' ', '.', ',', ':', ';', '-', '=', '+', '*', '#'];
+
+/// The power grid, searching for the best square one size per tick
+pub struct Day11 {
+    power: Vec<(Xy, i64)>,
+    sums: PowerSums,
+    size: usize,
+    best: (Xy, i64),
+    overall: (Xy, usize, i64),
+}
+
+impl Day11 {
+    pub fn new(input: &str) -> Result<Day11> {
+        let serial = parser::parse(input.as_bytes())?;
+        let power = grid_coords_iter()
+            .map(|xy| (xy, cell_power_calc(xy, serial)))
+            .collect::<Vec<_>>();
+        let mut day11 = Day11 {
+            power,
+            sums: PowerSums::new(serial),
+            size: 1,
+            best: ((1, 1), i64::MIN),
+            overall: ((1, 1), 1, i64::MIN),
+        };
+        day11.search();
+        Ok(day11)
+    }
+
+    /// Finds the best square of the current size
+    fn search(&mut self) {
+        let size = self.size;
+        self.best = grid_coords_iter()
+            .filter(|xy| xy.0 + size - 1 <= SIZE && xy.1 + size - 1 <= SIZE)
+            .map(|xy| (xy, self.sums.square(xy, size)))
+            .max_by_key(|&((x, y), power)| (power, std::cmp::Reverse((x, y))))
+            .unwrap();
+        if self.best.1 > self.overall.2 {
+            self.overall = (self.best.0, size, self.best.1);
+        }
+    }
+}
+
+impl super::Scene for Day11 {
+    fn step(&mut self) -> Result<bool> {
+        if self.size >= SIZE {
+            return Ok(false);
+        }
+        self.size += 1;
+        self.search();
+        Ok(true)
+    }
+
+    fn cells(&self) -> Vec<(Pos, char)> {
+        let shades = self
+            .power
+            .iter()
+            .map(|&((x, y), p)| ((x as i64, y as i64), SHADES[(p + 5) as usize]))
+            .filter(|&(_, c)| c != ' ');
+        // Outline of the best square of the current size
+        let ((x0, y0), s) = (
+            (self.best.0 .0 as i64, self.best.0 .1 as i64),
+            self.size as i64,
+        );
+        let outline = (0..s).flat_map(move |i| {
+            [
+                ((x0 + i, y0), '@'),
+                ((x0 + i, y0 + s - 1), '@'),
+                ((x0, y0 + i), '@'),
+                ((x0 + s - 1, y0 + i), '@'),
+            ]
+        });
+        shades.chain(outline).collect()
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "size {} best {:?} power {} | overall {:?},{} power {}",
+            self.size, self.best.0, self.best.1, self.overall.0, self.overall.1, self.overall.2
+        )
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::Scene;
+    let mut s = Day11::new("18\n")?;
+    s.step()?;
+    s.step()?;
+    assert_eq!(s.best, ((33, 45), 29));
+    while s.size < 16 {
+        s.step()?;
+    }
+    assert_eq!(s.overall, ((90, 269), 16, 113));
+    assert!(s.cells().contains(&((90, 269), '@')));
+    Ok(())
+}
@@ -0,0 +1,80 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+use anyhow::Result;
+
+use day13::*;
+
+use super::Pos;
+
+/// The carts moving over the tracks, crashed carts are removed
+pub struct Day13 {
+    grid: Grid,
+    carts: Vec<Cart>,
+    ticks: usize,
+    lastcrash: Option<(u16, u16)>,
+}
+
+impl Day13 {
+    pub fn new(input: &str) -> Result<Day13> {
+        let (grid, carts) = parser::parse(input.as_bytes())?;
+        Ok(Day13 {
+            grid,
+            carts,
+            ticks: 0,
+            lastcrash: None,
+        })
+    }
+}
+
+fn pos(qa: Qa) -> Pos {
+    let t = qa.tuple();
+    (i64::from(t.0), i64::from(t.1))
+}
+
+impl super::Scene for Day13 {
+    fn step(&mut self) -> Result<bool> {
+        if self.carts.len() < 2 {
+            return Ok(false);
+        }
+        self.ticks += 1;
+        if let Some(qa) = tick(&self.grid, &mut self.carts)?.last() {
+            self.lastcrash = Some(qa.tuple());
+        }
+        Ok(true)
+    }
+
+    fn cells(&self) -> Vec<(Pos, char)> {
+        let g = gridcarts(&self.grid, &self.carts);
+        let tracks = Qa::iter()
+            .filter(|&qa| g[qa] != ' ')
+            .map(|qa| (pos(qa), g[qa]));
+        // Carts go last so that they are kept when zoomed out
+        let carts = self.carts.iter().map(|c| (pos(c.qa), g[c.qa]));
+        tracks.chain(carts).collect()
+    }
+
+    fn status(&self) -> String {
+        let crash = self
+            .lastcrash
+            .map(|t| format!("{:?}", t))
+            .unwrap_or_else(|| "none".to_string());
+        format!(
+            "tick {} | carts {} | last crash {}",
+            self.ticks,
+            self.carts.len(),
+            crash
+        )
+    }
+}
+
+#[test]
+fn test() -> Result<()> {
+    use super::Scene;
+    let mut s = Day13::new(EXAMPLE)?;
+    assert!(s.cells().contains(&((2, 0), '>')));
+    while s.step()? {}
+    assert_eq!(s.status(), "tick 14 | carts 0 | last crash (7, 3)");
+    Ok(())
+}
@@ -0,0 +1,287 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+//! The day type and the solvers of all puzzle parts, shared by the
+//! runner and the C library
+
+use anyhow::anyhow;
+use anyhow::Result;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A puzzle day, parsed from `8`, `08` or `day08`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct Day(pub u8);
+
+impl FromStr for Day {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Day> {
+        let num = s.strip_prefix("day").unwrap_or(s);
+        let day = num
+            .parse::<u8>()
+            .map_err(|_| anyhow!("invalid day {:?}", s))?;
+        if !(1..=25).contains(&day) {
+            return Err(anyhow!("day out of range: {}", day));
+        }
+        Ok(Day(day))
+    }
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day{:02}", self.0)
+    }
+}
+
+impl Day {
+    /// The example input from the puzzle text, if the day has one
+    pub fn example(&self) -> Option<&'static str> {
+        match self.0 {
+            1 => Some(day01::EXAMPLE),
+            2 => Some(day02::EXAMPLE1),
+            3 => Some(day03::EXAMPLE),
+            4 => Some(day04::EXAMPLE),
+            5 => Some(day05::EXAMPLE),
+            6 => Some(day06::EXAMPLE),
+            7 => Some(day07::EXAMPLE),
+            8 => Some(day08::EXAMPLE),
+            9 => Some(day09::EXAMPLE),
+            10 => Some(day10::EXAMPLE),
+            11 => Some(day11::EXAMPLE),
+            12 => Some(day12::EXAMPLE),
+            13 => Some(day13::EXAMPLE),
+            _ => None,
+        }
+    }
+
+    /// Reads the input at `path`, or the example if no path is given
+    pub fn input(&self, path: Option<&Path>) -> Result<String> {
+        if let Some(path) = path {
+            Ok(fs::read_to_string(path)?)
+        } else {
+            self.example()
+                .map(String::from)
+                .ok_or_else(|| anyhow!("{} has no example, an input file is required", self))
+        }
+    }
+}
+
+#[test]
+fn test_day() -> Result<()> {
+    assert_eq!("8".parse::<Day>()?, Day(8));
+    assert_eq!("08".parse::<Day>()?, Day(8));
+    assert_eq!("day08".parse::<Day>()?, Day(8));
+    assert!("day26".parse::<Day>().is_err());
+    assert!("x".parse::<Day>().is_err());
+    assert_eq!(Day(8).to_string(), "day08");
+    Ok(())
+}
+
+/// Puzzle part, `a` or `b`
+pub type Part = char;
+
+/// Solver of a puzzle part, from the input text to the answer
+pub type Solve = fn(&str) -> Result<String>;
+
+pub struct Solver {
+    pub day: Day,
+    pub part: Part,
+    pub solve: Solve,
+}
+
+/// Formats a position as the puzzle expects it in the answer
+fn xy<T: std::fmt::Display>(t: (T, T)) -> String {
+    format!("{},{}", t.0, t.1)
+}
+
+/// Day 14 has no input file, it's just a number
+fn day14_input(input: &str) -> Result<&str> {
+    let input = input.trim();
+    if input.is_empty() || !input.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(anyhow!("error reading input: expected a number"));
+    }
+    Ok(input)
+}
+
+pub const SOLVERS: &[Solver] = &[
+    Solver {
+        day: Day(1),
+        part: 'a',
+        solve: |i| Ok(day01::process_a(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(1),
+        part: 'b',
+        solve: |i| Ok(day01::process_b(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(2),
+        part: 'a',
+        solve: |i| Ok(day02::process_a(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(2),
+        part: 'b',
+        solve: |i| day02::process_b(i.as_bytes()),
+    },
+    Solver {
+        day: Day(3),
+        part: 'a',
+        solve: |i| Ok(day03::process_a(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(3),
+        part: 'b',
+        solve: |i| Ok(day03::process_b(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(4),
+        part: 'a',
+        solve: |i| Ok(day04::process_a(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(4),
+        part: 'b',
+        solve: |i| Ok(day04::process_b(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(5),
+        part: 'a',
+        solve: |i| Ok(day05::process_a(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(5),
+        part: 'b',
+        solve: |i| Ok(day05::process_b(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(6),
+        part: 'a',
+        solve: |i| Ok(day06::process_a(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(6),
+        part: 'b',
+        solve: |i| Ok(day06::process_b(10000, i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(7),
+        part: 'a',
+        solve: |i| day07::process_a(i.as_bytes()),
+    },
+    Solver {
+        day: Day(7),
+        part: 'b',
+        solve: |i| Ok(day07::process_b(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(8),
+        part: 'a',
+        solve: |i| Ok(day08::process_a(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(8),
+        part: 'b',
+        solve: |i| Ok(day08::process_b(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(9),
+        part: 'a',
+        solve: |i| Ok(day09::process_a(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(9),
+        part: 'b',
+        solve: |i| Ok(day09::process_b(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(10),
+        part: 'a',
+        solve: |i| Ok(day10::process_a(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(10),
+        part: 'b',
+        solve: |i| Ok(day10::process_b(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(11),
+        part: 'a',
+        solve: |i| Ok(xy(day11::process_a(i.as_bytes())?)),
+    },
+    Solver {
+        day: Day(11),
+        part: 'b',
+        solve: |i| {
+            let (pos, size) = day11::process_b(i.as_bytes())?;
+            Ok(format!("{},{}", xy(pos), size))
+        },
+    },
+    Solver {
+        day: Day(12),
+        part: 'a',
+        solve: |i| Ok(day12::process_a(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(12),
+        part: 'b',
+        solve: |i| Ok(day12::process_b(i.as_bytes())?.to_string()),
+    },
+    Solver {
+        day: Day(13),
+        part: 'a',
+        solve: |i| Ok(xy(day13::process_a(i.as_bytes())?)),
+    },
+    Solver {
+        day: Day(13),
+        part: 'b',
+        solve: |i| Ok(xy(day13::process_b(i.as_bytes())?)),
+    },
+    Solver {
+        day: Day(14),
+        part: 'a',
+        solve: |i| {
+            let ignore = day14_input(i)?.parse()?;
+            let num = day14::process_a(ignore).map_err(|e| anyhow!("{}", e))?;
+            Ok(format!("{:010}", num))
+        },
+    },
+    Solver {
+        day: Day(14),
+        part: 'b',
+        solve: |i| {
+            let suffix = day14_input(i)?.as_bytes();
+            Ok(day14::process_b(suffix)
+                .map_err(|e| anyhow!("{}", e))?
+                .to_string())
+        },
+    },
+];
+
+pub fn solver(day: Day, part: Part) -> Result<&'static Solver> {
+    SOLVERS
+        .iter()
+        .find(|s| s.day == day && s.part == part)
+        .ok_or_else(|| anyhow!("no solver for {}{}", day, part))
+}
+
+pub fn solve(day: Day, part: Part, input: &str) -> Result<String> {
+    (solver(day, part)?.solve)(input)
+}
+
+#[test]
+fn test() -> Result<()> {
+    assert_eq!(solve(Day(1), 'a', day01::EXAMPLE)?, "3");
+    assert_eq!(solve(Day(7), 'a', day07::EXAMPLE)?, "CABDFE");
+    assert_eq!(solve(Day(11), 'a', "18\n")?, "33,45");
+    assert_eq!(solve(Day(13), 'b', day13::EXAMPLE2)?, "6,4");
+    assert_eq!(solve(Day(14), 'a', "9\n")?, "5158916779");
+    assert_eq!(solve(Day(14), 'b', "59414")?, "2018");
+    assert!(solve(Day(14), 'a', "x").is_err());
+    assert!(solve(Day(15), 'a', "").is_err());
+    assert!(solve(Day(3), 'a', "bogus").is_err());
+    Ok(())
+}
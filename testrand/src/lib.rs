@@ -0,0 +1,72 @@
+// Copyright (C) 2023 Leandro Lisboa Penz <lpenz@lpenz.org>
+// This file is subject to the terms and conditions defined in
+// file 'LICENSE', which is part of this source code package.
+
+//! Pseudo-random numbers for the differential tests of the days,
+//! deterministic so that any failure can be reproduced.
+
+use std::fmt;
+
+/// Linear congruential generator with Knuth's MMIX constants
+#[derive(Debug, Clone)]
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Lcg {
+        Lcg(seed)
+    }
+
+    /// Number in 0..max, taken from the high bits, which have the
+    /// longest periods
+    pub fn below(&mut self, max: u64) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1);
+        (self.0 >> 33) % max
+    }
+}
+
+/// Seed of the differential tests
+pub const SEED: u64 = 0x2018;
+
+/// Differential test: checks that fast and simple give the same output
+/// for the given number of inputs made by input, showing the input
+/// that fails
+pub fn differential<I, O>(
+    cases: usize,
+    mut input: impl FnMut(&mut Lcg) -> I,
+    mut fast: impl FnMut(&I) -> O,
+    mut simple: impl FnMut(&I) -> O,
+) where
+    I: fmt::Debug,
+    O: PartialEq + fmt::Debug,
+{
+    let mut rng = Lcg::new(SEED);
+    for _ in 0..cases {
+        let i = input(&mut rng);
+        assert_eq!(fast(&i), simple(&i), "input {:?}", i);
+    }
+}
+
+#[test]
+fn test() {
+    let mut a = Lcg::new(2018);
+    let mut b = a.clone();
+    let xs = (0..100).map(|_| a.below(10)).collect::<Vec<_>>();
+    assert!(xs.iter().all(|&x| x < 10));
+    assert_eq!(xs, (0..100).map(|_| b.below(10)).collect::<Vec<_>>());
+    assert_ne!(xs, vec![xs[0]; 100]);
+}
+
+#[test]
+#[should_panic(expected = "input 4")]
+fn test_differential() {
+    let mut n = 0;
+    differential(
+        10,
+        |_| {
+            n += 1;
+            n
+        },
+        |&i| i.min(3),
+        |&i| i,
+    );
+}